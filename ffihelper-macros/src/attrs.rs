@@ -0,0 +1,53 @@
+use syn::{Attribute, Ident, Result};
+
+#[derive(Default)]
+pub struct ContainerAttrs {
+    /// Prefix the record with its encoded length and field count so fields can be added without
+    /// breaking readers.
+    pub extensible: bool,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut res = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("ffi")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("extensible") {
+                    res.extensible = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported ffi attribute"))
+                }
+            })?;
+        }
+        Ok(res)
+    }
+}
+
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Describe this named type by reference in the metadata, for mutually recursive types.
+    pub type_ref: Option<Ident>,
+    /// Use `Default::default()` when the field is missing from an extensible record.
+    pub default: bool,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut res = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("ffi")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("type_ref") {
+                    res.type_ref = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    res.default = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported ffi field attribute"))
+                }
+            })?;
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,329 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    punctuated::Punctuated, Data, DataEnum, DeriveInput, Error, Fields, Meta, Result, Token,
+};
+
+use crate::{
+    attrs::{ContainerAttrs, FieldAttrs},
+    type_ref::TypeRefs,
+    util::{ffi_generics, ffihelper, field_members, ut},
+};
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let data = enum_data(&input, "FFIEnum")?;
+    let converters = expand_converters(&input, data)?;
+    let type_id = expand_type_id(&input, data, false)?;
+    Ok(quote! {
+        #converters
+        #type_id
+    })
+}
+
+pub fn expand_error(input: DeriveInput) -> Result<TokenStream> {
+    let data = enum_data(&input, "FFIError")?;
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let generics = ffi_generics(&input.generics, quote!(#ffihelper::Lower));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let converters = expand_converters(&input, data)?;
+    let type_id = expand_type_id(&input, data, true)?;
+    Ok(quote! {
+        #converters
+        #type_id
+
+        impl #impl_generics #ffihelper::LowerError<#ut> for #ident #ty_generics #where_clause {
+            fn lower_error(obj: Self) -> #ffihelper::ffi::buffer::FFIBuffer {
+                <Self as #ffihelper::Lower<#ut>>::lower_into_buffer(obj)
+            }
+        }
+    })
+}
+
+fn expand_converters(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    Ok(if is_flat(data) {
+        let check = expand_flat_check(input, data)?;
+        let lower = expand_flat_lower(input);
+        let lift = expand_flat_lift(input, data);
+        quote! {
+            #check
+            #lower
+            #lift
+        }
+    } else {
+        let lower = expand_lower(input, data);
+        let lift = expand_lift(input, data);
+        quote! {
+            #lower
+            #lift
+        }
+    })
+}
+
+fn enum_data<'a>(input: &'a DeriveInput, derive: &str) -> Result<&'a DataEnum> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            let msg = format!("{derive} can only be derived for enums");
+            return Err(Error::new_spanned(input, msg));
+        }
+    };
+    if data.variants.is_empty() {
+        let msg = format!("{derive} cannot be derived for empty enums");
+        return Err(Error::new_spanned(input, msg));
+    }
+    // Only records carry a length prefix, so enums can't be extended or have defaulted fields.
+    if ContainerAttrs::parse(&input.attrs)?.extensible {
+        let msg = format!("#[ffi(extensible)] is not supported by {derive}");
+        return Err(Error::new_spanned(input, msg));
+    }
+    for field in data.variants.iter().flat_map(|v| v.fields.iter()) {
+        if FieldAttrs::parse(&field.attrs)?.default {
+            let msg = format!("#[ffi(default)] is not supported by {derive}");
+            return Err(Error::new_spanned(field, msg));
+        }
+    }
+    Ok(data)
+}
+
+/// Enums without any fields are passed as their `i32` discriminant.
+pub fn is_flat(data: &DataEnum) -> bool {
+    data.variants
+        .iter()
+        .all(|v| matches!(v.fields, Fields::Unit))
+}
+
+/// Flat enums are passed as `i32`, so wider discriminants would be silently truncated.
+///
+/// Each discriminant is range-checked at compile time through an `i128` cast, which is exact for
+/// every representation except `u128`, so that one is rejected outright.
+fn expand_flat_check(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if let Some(meta) = metas.iter().find(|meta| meta.path().is_ident("u128")) {
+            let msg = "#[repr(u128)] discriminants can't be checked to fit in the i32 FFIType";
+            return Err(Error::new_spanned(meta, msg));
+        }
+    }
+
+    let ident = &input.ident;
+    let checks = data.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+        let msg = format!("discriminant of {ident}::{variant_ident} doesn't fit in i32");
+        quote! {
+            ::std::assert!(
+                #ident::#variant_ident as i128 >= i32::MIN as i128
+                    && #ident::#variant_ident as i128 <= i32::MAX as i128,
+                #msg,
+            );
+        }
+    });
+    Ok(quote! {
+        const _: () = {
+            #(#checks)*
+        };
+    })
+}
+
+fn expand_flat_lower(input: &DeriveInput) -> TokenStream {
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let generics = ffi_generics(&input.generics, quote!(#ffihelper::Lower));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    quote! {
+        unsafe impl #impl_generics #ffihelper::Lower<#ut> for #ident #ty_generics #where_clause {
+            type FFIType = i32;
+            fn lower(obj: Self) -> Self::FFIType {
+                obj as i32
+            }
+            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+                <i32 as #ffihelper::Lower<#ut>>::write(obj as i32, buf)
+            }
+        }
+    }
+}
+
+fn expand_flat_lift(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let generics = ffi_generics(&input.generics, quote!(#ffihelper::Lift));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let variants: Vec<_> = data.variants.iter().map(|v| &v.ident).collect();
+
+    quote! {
+        unsafe impl #impl_generics #ffihelper::Lift<#ut> for #ident #ty_generics #where_clause {
+            type FFIType = i32;
+            fn try_lift(v: Self::FFIType) -> #ffihelper::FFIResult<Self> {
+                Ok(match v {
+                    #(v if v == Self::#variants as i32 => Self::#variants,)*
+                    v => #ffihelper::anyhow::bail!("unknown discriminant {} for enum {}", v, #name),
+                })
+            }
+            fn try_read(buf: &mut &[u8]) -> #ffihelper::FFIResult<Self> {
+                <Self as #ffihelper::Lift<#ut>>::try_lift(<i32 as #ffihelper::Lift<#ut>>::try_read(buf)?)
+            }
+        }
+    }
+}
+
+fn expand_lower(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let generics = ffi_generics(&input.generics, quote!(#ffihelper::Lower));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+        let variant_ident = &variant.ident;
+        let tag = i as i32;
+        let members: Vec<_> = field_members(&variant.fields)
+            .into_iter()
+            .map(|(member, _)| member)
+            .collect();
+        let bindings: Vec<_> = (0..members.len())
+            .map(|i| format_ident!("__f{}", i))
+            .collect();
+        let tys: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+        quote! {
+            Self::#variant_ident { #(#members: #bindings),* } => {
+                <i32 as #ffihelper::Lower<#ut>>::write(#tag, buf);
+                #(<#tys as #ffihelper::Lower<#ut>>::write(#bindings, buf);)*
+            }
+        }
+    });
+
+    quote! {
+        unsafe impl #impl_generics #ffihelper::Lower<#ut> for #ident #ty_generics #where_clause {
+            type FFIType = #ffihelper::ffi::buffer::FFIBuffer;
+            fn lower(obj: Self) -> Self::FFIType {
+                <Self as #ffihelper::Lower<#ut>>::lower_into_buffer(obj)
+            }
+            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+                match obj {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+fn expand_lift(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let generics = ffi_generics(&input.generics, quote!(#ffihelper::Lift));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+        let variant_ident = &variant.ident;
+        let tag = i as i32;
+        let members: Vec<_> = field_members(&variant.fields)
+            .into_iter()
+            .map(|(member, _)| member)
+            .collect();
+        let tys: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+        quote! {
+            #tag => Self::#variant_ident {
+                #(#members: <#tys as #ffihelper::Lift<#ut>>::try_read(buf)?,)*
+            },
+        }
+    });
+
+    quote! {
+        unsafe impl #impl_generics #ffihelper::Lift<#ut> for #ident #ty_generics #where_clause {
+            type FFIType = #ffihelper::ffi::buffer::FFIBuffer;
+            fn try_lift(v: Self::FFIType) -> #ffihelper::FFIResult<Self> {
+                <Self as #ffihelper::Lift<#ut>>::try_lift_from_buffer(v)
+            }
+            fn try_read(buf: &mut &[u8]) -> #ffihelper::FFIResult<Self> {
+                Ok(match <i32 as #ffihelper::Lift<#ut>>::try_read(buf)? {
+                    #(#arms)*
+                    v => #ffihelper::anyhow::bail!("unknown variant tag {} for enum {}", v, #name),
+                })
+            }
+        }
+    }
+}
+
+fn expand_type_id(input: &DeriveInput, data: &DataEnum, is_error: bool) -> Result<TokenStream> {
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let generics = ffi_generics(&input.generics, quote!(#ffihelper::TypeId));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let flat = is_flat(data);
+    let type_refs = TypeRefs::new(input)?;
+    let type_ref_markers = type_refs.markers();
+    let variant_count = u8::try_from(data.variants.len())
+        .map_err(|_| Error::new(Span::call_site(), "too many variants for FFIEnum"))?;
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            let variant_name = variant_ident.to_string();
+            let discriminant =
+                flat.then(|| quote!(.concat_u32(#ident::#variant_ident as i32 as u32)));
+            let field_count = u8::try_from(variant.fields.len()).map_err(|_| {
+                Error::new_spanned(&variant.fields, "too many fields for FFIEnum variant")
+            })?;
+            let field_names: Vec<_> = field_members(&variant.fields)
+                .into_iter()
+                .map(|(_, name)| name)
+                .collect();
+            let tys: Vec<_> = variant
+                .fields
+                .iter()
+                .map(|f| type_refs.meta_ty(&f.ty))
+                .collect();
+            Ok(quote! {
+                .concat_str(#variant_name)
+                #discriminant
+                .concat_value(#field_count)
+                #(.concat_str(#field_names)
+                .concat(<#tys as #ffihelper::TypeId<#ut>>::TYPE_ID_META))*
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let codes = quote!(#ffihelper::metadata::codes);
+    let header = if is_error {
+        quote!(from_code(#codes::TYPE_ERROR).concat_value(#codes::TYPE_ENUM))
+    } else {
+        quote!(from_code(#codes::TYPE_ENUM))
+    };
+
+    Ok(quote! {
+        const _: () = {
+            #type_ref_markers
+
+            impl #impl_generics #ffihelper::TypeId<#ut> for #ident #ty_generics #where_clause {
+                const TYPE_ID_META: #ffihelper::metadata::MetadataBuffer =
+                    #ffihelper::metadata::MetadataBuffer::#header
+                        .concat_str(#name)
+                        .concat_bool(#flat)
+                        .concat_value(#variant_count)
+                        #(#variants)*;
+            }
+        };
+    })
+}
@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, FnArg, ItemTrait, Pat, Result, ReturnType, TraitItem, Type, TypeParamBound};
+
+use crate::util::{ffihelper, ut};
+
+pub fn expand(item: ItemTrait) -> Result<TokenStream> {
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &item.ident;
+    let name = ident.to_string();
+    if !item.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "ffi_trait does not support generic traits",
+        ));
+    }
+    // Objects are shared across threads by foreign code, so `dyn Trait` must be `Send + Sync`.
+    for required in ["Send", "Sync"] {
+        let found = item.supertraits.iter().any(|bound| match bound {
+            TypeParamBound::Trait(bound) => bound
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == required),
+            _ => false,
+        });
+        if !found {
+            let msg = format!("ffi_trait requires `{required}` as a supertrait");
+            return Err(Error::new_spanned(ident, msg));
+        }
+    }
+
+    let mut methods = Vec::new();
+    for trait_item in &item.items {
+        let TraitItem::Fn(method) = trait_item else {
+            continue;
+        };
+        let sig = &method.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                &sig.generics,
+                "ffi_trait methods cannot be generic",
+            ));
+        }
+        let mut inputs = sig.inputs.iter();
+        match inputs.next() {
+            Some(FnArg::Receiver(receiver))
+                if receiver.reference.is_some() && receiver.mutability.is_none() => {}
+            _ => {
+                return Err(Error::new_spanned(
+                    sig,
+                    "ffi_trait methods must take `&self`",
+                ))
+            }
+        }
+        let mut arg_names = Vec::new();
+        let mut arg_tys = Vec::new();
+        for input in inputs {
+            let FnArg::Typed(arg) = input else {
+                unreachable!("receiver can only be the first argument");
+            };
+            let Pat::Ident(pat) = &*arg.pat else {
+                return Err(Error::new_spanned(
+                    &arg.pat,
+                    "ffi_trait arguments must be named",
+                ));
+            };
+            arg_names.push(pat.ident.to_string());
+            arg_tys.push(&*arg.ty);
+        }
+        let arg_count = u8::try_from(arg_names.len()).map_err(|_| {
+            Error::new_spanned(&sig.inputs, "too many arguments for ffi_trait method")
+        })?;
+        let return_ty: Type = match &sig.output {
+            ReturnType::Default => syn::parse_quote!(()),
+            ReturnType::Type(_, ty) => (**ty).clone(),
+        };
+        let method_name = sig.ident.to_string();
+        methods.push(quote! {
+            .concat_str(#method_name)
+            .concat_value(#arg_count)
+            #(.concat_str(#arg_names)
+            .concat(<#arg_tys as #ffihelper::TypeId<#ut>>::TYPE_ID_META))*
+            .concat(<#return_ty as #ffihelper::TypeId<#ut>>::TYPE_ID_META)
+        });
+    }
+    let method_count = u8::try_from(methods.len())
+        .map_err(|_| Error::new_spanned(&item, "too many methods for ffi_trait"))?;
+
+    Ok(quote! {
+        #item
+
+        #ffihelper::ffi_object!(dyn #ident, handle_map);
+
+        impl<#ut> #ffihelper::TypeId<#ut> for dyn #ident {
+            const TYPE_ID_META: #ffihelper::metadata::MetadataBuffer =
+                #ffihelper::metadata::MetadataBuffer::from_code(#ffihelper::metadata::codes::TYPE_INTERFACE)
+                    .concat_str(#name)
+                    .concat_value(<<dyn #ident as #ffihelper::FFIObject>::Handle as #ffihelper::ObjectHandle<dyn #ident>>::KIND)
+                    .concat_value(#method_count)
+                    #(#methods)*;
+        }
+    })
+}
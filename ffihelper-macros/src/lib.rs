@@ -0,0 +1,41 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemTrait};
+
+mod attrs;
+mod enum_;
+mod interface;
+mod record;
+mod type_ref;
+mod util;
+
+#[proc_macro_derive(FFIRecord, attributes(ffi))]
+pub fn derive_ffi_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    record::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FFIEnum, attributes(ffi))]
+pub fn derive_ffi_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    enum_::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(FFIError, attributes(ffi))]
+pub fn derive_ffi_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    enum_::expand_error(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn ffi_trait(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemTrait);
+    interface::expand(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,149 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Result};
+
+use crate::{
+    attrs::{ContainerAttrs, FieldAttrs},
+    type_ref::TypeRefs,
+    util::{ffi_generics, ffihelper, field_members, ut},
+};
+
+pub fn expand(input: DeriveInput) -> Result<TokenStream> {
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "FFIRecord can only be derived for structs",
+            ))
+        }
+    };
+    let ffihelper = ffihelper();
+    let ut = ut();
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let field_count = u8::try_from(data.fields.len())
+        .map_err(|_| Error::new_spanned(&data.fields, "too many fields for FFIRecord"))?;
+    let members = field_members(&data.fields);
+    let field_tys: Vec<_> = data.fields.iter().map(|f| &f.ty).collect();
+    let type_refs = TypeRefs::new(&input)?;
+    let type_ref_markers = type_refs.markers();
+    let field_meta_tys: Vec<_> = field_tys.iter().map(|ty| type_refs.meta_ty(ty)).collect();
+    let field_members: Vec<_> = members.iter().map(|(member, _)| member).collect();
+    let field_names: Vec<_> = members.iter().map(|(_, name)| name).collect();
+
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let extensible = attrs.extensible;
+    let mut field_defaults = Vec::with_capacity(data.fields.len());
+    for field in &data.fields {
+        let field_attrs = FieldAttrs::parse(&field.attrs)?;
+        if field_attrs.default && !extensible {
+            return Err(Error::new_spanned(
+                field,
+                "#[ffi(default)] requires the record to be #[ffi(extensible)]",
+            ));
+        }
+        field_defaults.push(field_attrs.default);
+    }
+
+    let (write, try_read) = if extensible {
+        let read_fields = members
+            .iter()
+            .zip(&field_tys)
+            .zip(&field_defaults)
+            .enumerate()
+            .map(|(i, (((member, field_name), ty), default))| {
+                let index = i as u8;
+                let missing = if *default {
+                    quote!(::std::default::Default::default())
+                } else {
+                    quote!(#ffihelper::anyhow::bail!("missing field {} for record {}", #field_name, #name))
+                };
+                quote! {
+                    #member: if #index < count {
+                        <#ty as #ffihelper::Lift<#ut>>::try_read(&mut body)?
+                    } else {
+                        #missing
+                    },
+                }
+            });
+        (
+            quote! {
+                let mut body = ::std::vec::Vec::new();
+                <u8 as #ffihelper::Lower<#ut>>::write(#field_count, &mut body);
+                #(<#field_tys as #ffihelper::Lower<#ut>>::write(obj.#field_members, &mut body);)*
+                <i32 as #ffihelper::Lower<#ut>>::write(i32::try_from(body.len()).unwrap(), buf);
+                buf.extend_from_slice(&body);
+            },
+            // The body starts with the number of fields the writer knew about. Fields past that
+            // count are defaulted, unknown trailing fields are skipped along with the body.
+            quote! {
+                let len = usize::try_from(<i32 as #ffihelper::Lift<#ut>>::try_read(buf)?)?;
+                #ffihelper::check_remaining(buf, len)?;
+                let (mut body, rest) = buf.split_at(len);
+                *buf = rest;
+                let count = <u8 as #ffihelper::Lift<#ut>>::try_read(&mut body)?;
+                Ok(Self {
+                    #(#read_fields)*
+                })
+            },
+        )
+    } else {
+        (
+            quote! {
+                #(<#field_tys as #ffihelper::Lower<#ut>>::write(obj.#field_members, buf);)*
+            },
+            quote! {
+                Ok(Self {
+                    #(#field_members: <#field_tys as #ffihelper::Lift<#ut>>::try_read(buf)?,)*
+                })
+            },
+        )
+    };
+
+    let lower_generics = ffi_generics(&input.generics, quote!(#ffihelper::Lower));
+    let (lower_impl, _, lower_where) = lower_generics.split_for_impl();
+    let lift_generics = ffi_generics(&input.generics, quote!(#ffihelper::Lift));
+    let (lift_impl, _, lift_where) = lift_generics.split_for_impl();
+    let type_id_generics = ffi_generics(&input.generics, quote!(#ffihelper::TypeId));
+    let (type_id_impl, _, type_id_where) = type_id_generics.split_for_impl();
+
+    Ok(quote! {
+        unsafe impl #lower_impl #ffihelper::Lower<#ut> for #ident #ty_generics #lower_where {
+            type FFIType = #ffihelper::ffi::buffer::FFIBuffer;
+            fn lower(obj: Self) -> Self::FFIType {
+                <Self as #ffihelper::Lower<#ut>>::lower_into_buffer(obj)
+            }
+            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+                #write
+            }
+        }
+
+        unsafe impl #lift_impl #ffihelper::Lift<#ut> for #ident #ty_generics #lift_where {
+            type FFIType = #ffihelper::ffi::buffer::FFIBuffer;
+            fn try_lift(v: Self::FFIType) -> #ffihelper::FFIResult<Self> {
+                <Self as #ffihelper::Lift<#ut>>::try_lift_from_buffer(v)
+            }
+            fn try_read(buf: &mut &[u8]) -> #ffihelper::FFIResult<Self> {
+                #try_read
+            }
+        }
+
+        const _: () = {
+            #type_ref_markers
+
+            impl #type_id_impl #ffihelper::TypeId<#ut> for #ident #ty_generics #type_id_where {
+                const TYPE_ID_META: #ffihelper::metadata::MetadataBuffer =
+                    #ffihelper::metadata::MetadataBuffer::from_code(#ffihelper::metadata::codes::TYPE_RECORD)
+                        .concat_str(#name)
+                        .concat_bool(#extensible)
+                        .concat_value(#field_count)
+                        #(.concat_str(#field_names)
+                        .concat_bool(#field_defaults)
+                        .concat(<#field_meta_tys as #ffihelper::TypeId<#ut>>::TYPE_ID_META))*;
+            }
+        };
+    })
+}
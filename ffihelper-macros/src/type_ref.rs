@@ -0,0 +1,98 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    Data, DeriveInput, Ident, Result, Type,
+};
+
+use crate::{
+    attrs::FieldAttrs,
+    util::{ffihelper, ut},
+};
+
+/// Named types that are described by a back-reference instead of being inlined in the metadata.
+///
+/// Inlining the metadata of a recursive type would never terminate, so the type itself (and any
+/// type named by `#[ffi(type_ref = ...)]`) is replaced by a marker type emitting `TYPE_REF`.
+pub struct TypeRefs {
+    idents: Vec<Ident>,
+}
+
+impl TypeRefs {
+    pub fn new(input: &DeriveInput) -> Result<Self> {
+        let mut idents = vec![input.ident.clone()];
+        let fields: Vec<_> = match &input.data {
+            Data::Struct(data) => data.fields.iter().collect(),
+            Data::Enum(data) => data.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+            Data::Union(data) => data.fields.named.iter().collect(),
+        };
+        for field in fields {
+            if let Some(ident) = FieldAttrs::parse(&field.attrs)?.type_ref {
+                if !idents.contains(&ident) {
+                    idents.push(ident);
+                }
+            }
+        }
+        Ok(Self { idents })
+    }
+
+    /// The type whose `TYPE_ID_META` describes `ty` in the metadata.
+    pub fn meta_ty(&self, ty: &Type) -> Type {
+        let mut ty = ty.clone();
+        ReplaceRefs(&self.idents).visit_type_mut(&mut ty);
+        ty
+    }
+
+    pub fn markers(&self) -> TokenStream {
+        let ffihelper = ffihelper();
+        let ut = ut();
+        let markers = self.idents.iter().map(|ident| {
+            let marker = marker_ident(ident);
+            let name = ident.to_string();
+            quote! {
+                #[allow(dead_code)]
+                struct #marker;
+
+                impl<#ut> #ffihelper::TypeId<#ut> for #marker {
+                    const TYPE_ID_META: #ffihelper::metadata::MetadataBuffer =
+                        #ffihelper::metadata::MetadataBuffer::from_code(#ffihelper::metadata::codes::TYPE_REF)
+                            .concat_str(#name);
+                }
+            }
+        });
+        quote!(#(#markers)*)
+    }
+}
+
+struct ReplaceRefs<'a>(&'a [Ident]);
+
+impl VisitMut for ReplaceRefs<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(path) = ty {
+            if path.qself.is_none() {
+                if path.path.is_ident("Self") {
+                    let marker = marker_ident(&self.0[0]);
+                    *ty = parse_quote!(#marker);
+                    return;
+                }
+                // Only bare names can refer to the listed types, a qualified path like
+                // `std::time::Duration` is a different type even if its last segment matches.
+                let segments = &path.path.segments;
+                if path.path.leading_colon.is_none() && segments.len() == 1 {
+                    let ident = &segments[0].ident;
+                    if self.0.contains(ident) {
+                        let marker = marker_ident(ident);
+                        *ty = parse_quote!(#marker);
+                        return;
+                    }
+                }
+            }
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+}
+
+fn marker_ident(ident: &Ident) -> Ident {
+    format_ident!("__FFITypeRef{}", ident)
+}
@@ -0,0 +1,34 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, Fields, Generics, Ident, Index, Member};
+
+pub fn ffihelper() -> TokenStream {
+    quote!(::ffihelper)
+}
+
+pub fn ut() -> Ident {
+    Ident::new("UT", Span::call_site())
+}
+
+/// Adds the `UT` parameter to `generics` and bounds every type parameter by `bound<UT>`.
+pub fn ffi_generics(generics: &Generics, bound: TokenStream) -> Generics {
+    let ut = ut();
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound<#ut>));
+    }
+    generics.params.insert(0, parse_quote!(#ut));
+    generics
+}
+
+/// The member used to access each field, along with the name recorded in the metadata.
+pub fn field_members(fields: &Fields) -> Vec<(Member, String)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+            None => (Member::Unnamed(Index::from(i)), i.to_string()),
+        })
+        .collect()
+}
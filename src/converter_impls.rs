@@ -1,15 +1,33 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    ffi::{c_char, CString, OsString},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    },
+    ops::{Bound, Range, RangeInclusive},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use anyhow::bail;
 use bytes::{Buf, BufMut};
 
 use crate::{
     check_remaining,
-    converter_traits::{FFIConverter, Lift, Lower, TypeId},
+    converter_traits::{FFIConverter, FFIObject, Lift, Lower, ObjectHandle, TypeId},
     derive_ffi_traits,
-    ffi::buffer::FFIBuffer,
+    ffi::{
+        buffer::FFIBuffer,
+        cstring::FFICStr,
+        int128::{FFII128, FFIU128},
+    },
     metadata,
     metadata::MetadataBuffer,
+    types::{BoundRange, Bytes, CStrArg, Utf16String},
     FFIResult,
 };
 
@@ -53,6 +71,158 @@ impl_ffi_converter_for_num_primitive!(i64, metadata::codes::TYPE_I64);
 impl_ffi_converter_for_num_primitive!(f32, metadata::codes::TYPE_F32);
 impl_ffi_converter_for_num_primitive!(f64, metadata::codes::TYPE_F64);
 
+macro_rules! impl_ffi_converter_for_wide_num_primitive {
+    ($T:ty, $FFIType:ty, $type_code:expr) => {
+        paste::paste! {
+            unsafe impl<UT> FFIConverter<UT> for $T {
+                type FFIType = $FFIType;
+
+                fn lower(obj: $T) -> Self::FFIType {
+                    <$FFIType>::from(obj)
+                }
+
+                fn try_lift(v: Self::FFIType) -> FFIResult<$T> {
+                    Ok(<$T>::from(v))
+                }
+
+                fn write(obj: $T, buf: &mut Vec<u8>) {
+                    buf.[<put_ $T>](obj);
+                }
+
+                fn try_read(buf: &mut &[u8]) -> FFIResult<$T> {
+                    check_remaining(buf, std::mem::size_of::<$T>())?;
+                    Ok(buf.[<get_ $T>]())
+                }
+
+                const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code($type_code);
+            }
+        }
+    };
+}
+
+impl_ffi_converter_for_wide_num_primitive!(u128, FFIU128, metadata::codes::TYPE_U128);
+impl_ffi_converter_for_wide_num_primitive!(i128, FFII128, metadata::codes::TYPE_I128);
+
+macro_rules! impl_ffi_converter_for_pointer_sized_primitive {
+    ($T:ty, $FFIType:ty, $type_code:expr) => {
+        paste::paste! {
+            unsafe impl<UT> FFIConverter<UT> for $T {
+                type FFIType = $FFIType;
+
+                fn lower(obj: $T) -> Self::FFIType {
+                    <$FFIType>::try_from(obj).unwrap()
+                }
+
+                fn try_lift(v: Self::FFIType) -> FFIResult<$T> {
+                    match <$T>::try_from(v) {
+                        Ok(v) => Ok(v),
+                        Err(_) => bail!(concat!("value {} out of range for ", stringify!($T)), v),
+                    }
+                }
+
+                fn write(obj: $T, buf: &mut Vec<u8>) {
+                    buf.[<put_ $FFIType>](<Self as FFIConverter<UT>>::lower(obj));
+                }
+
+                fn try_read(buf: &mut &[u8]) -> FFIResult<$T> {
+                    check_remaining(buf, std::mem::size_of::<$FFIType>())?;
+                    <Self as FFIConverter<UT>>::try_lift(buf.[<get_ $FFIType>]())
+                }
+
+                const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code($type_code);
+            }
+        }
+    };
+}
+
+impl_ffi_converter_for_pointer_sized_primitive!(usize, u64, metadata::codes::TYPE_USIZE);
+impl_ffi_converter_for_pointer_sized_primitive!(isize, i64, metadata::codes::TYPE_ISIZE);
+
+macro_rules! impl_ffi_converter_for_non_zero {
+    ($T:ty, $Prim:ty) => {
+        unsafe impl<UT> FFIConverter<UT> for $T {
+            type FFIType = <$Prim as FFIConverter<UT>>::FFIType;
+
+            fn lower(obj: $T) -> Self::FFIType {
+                <$Prim as FFIConverter<UT>>::lower(obj.get())
+            }
+
+            fn try_lift(v: Self::FFIType) -> FFIResult<$T> {
+                match <$T>::new(<$Prim as FFIConverter<UT>>::try_lift(v)?) {
+                    Some(v) => Ok(v),
+                    None => bail!(concat!("unexpected zero value for ", stringify!($T))),
+                }
+            }
+
+            fn write(obj: $T, buf: &mut Vec<u8>) {
+                <$Prim as FFIConverter<UT>>::write(obj.get(), buf)
+            }
+
+            fn try_read(buf: &mut &[u8]) -> FFIResult<$T> {
+                match <$T>::new(<$Prim as FFIConverter<UT>>::try_read(buf)?) {
+                    Some(v) => Ok(v),
+                    None => bail!(concat!("unexpected zero value for ", stringify!($T))),
+                }
+            }
+
+            const TYPE_ID_META: MetadataBuffer =
+                MetadataBuffer::from_code(metadata::codes::TYPE_NON_ZERO)
+                    .concat(<$Prim as FFIConverter<UT>>::TYPE_ID_META);
+        }
+    };
+}
+
+impl_ffi_converter_for_non_zero!(NonZeroU8, u8);
+impl_ffi_converter_for_non_zero!(NonZeroI8, i8);
+impl_ffi_converter_for_non_zero!(NonZeroU16, u16);
+impl_ffi_converter_for_non_zero!(NonZeroI16, i16);
+impl_ffi_converter_for_non_zero!(NonZeroU32, u32);
+impl_ffi_converter_for_non_zero!(NonZeroI32, i32);
+impl_ffi_converter_for_non_zero!(NonZeroU64, u64);
+impl_ffi_converter_for_non_zero!(NonZeroI64, i64);
+impl_ffi_converter_for_non_zero!(NonZeroU128, u128);
+impl_ffi_converter_for_non_zero!(NonZeroI128, i128);
+impl_ffi_converter_for_non_zero!(NonZeroUsize, usize);
+impl_ffi_converter_for_non_zero!(NonZeroIsize, isize);
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Wrapping<T> {
+    type FFIType = T::FFIType;
+    fn lower(obj: Wrapping<T>) -> Self::FFIType {
+        T::lower(obj.0)
+    }
+    fn write(obj: Wrapping<T>, buf: &mut Vec<u8>) {
+        T::write(obj.0, buf)
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Wrapping<T> {
+    type FFIType = T::FFIType;
+    fn try_lift(v: Self::FFIType) -> FFIResult<Wrapping<T>> {
+        T::try_lift(v).map(Wrapping)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Wrapping<T>> {
+        T::try_read(buf).map(Wrapping)
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for Wrapping<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_WRAPPING).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT> FFIConverter<UT> for () {
+    type FFIType = ();
+    fn lower(_obj: ()) -> Self::FFIType {}
+    fn write(_obj: (), _buf: &mut Vec<u8>) {}
+    fn try_lift(_v: Self::FFIType) -> FFIResult<()> {
+        Ok(())
+    }
+    fn try_read(_buf: &mut &[u8]) -> FFIResult<()> {
+        Ok(())
+    }
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_UNIT);
+}
+
 unsafe impl<UT> FFIConverter<UT> for bool {
     type FFIType = i8;
     fn lower(obj: bool) -> Self::FFIType {
@@ -75,6 +245,27 @@ unsafe impl<UT> FFIConverter<UT> for bool {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BOOL);
 }
 
+unsafe impl<UT> FFIConverter<UT> for char {
+    type FFIType = u32;
+    fn lower(obj: char) -> Self::FFIType {
+        u32::from(obj)
+    }
+    fn write(obj: char, buf: &mut Vec<u8>) {
+        buf.put_u32(<Self as FFIConverter<UT>>::lower(obj));
+    }
+    fn try_lift(v: Self::FFIType) -> FFIResult<char> {
+        match char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => bail!("invalid unicode scalar value for char ({v:#x})"),
+        }
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<char> {
+        check_remaining(buf, 4)?;
+        <Self as FFIConverter<UT>>::try_lift(buf.get_u32())
+    }
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_CHAR);
+}
+
 unsafe impl<UT> FFIConverter<UT> for String {
     type FFIType = FFIBuffer;
 
@@ -89,8 +280,7 @@ unsafe impl<UT> FFIConverter<UT> for String {
     }
 
     fn try_lift(v: Self::FFIType) -> FFIResult<String> {
-        let v = v.destroy_into_vec();
-        Ok(unsafe { String::from_utf8_unchecked(v) })
+        Ok(String::from_utf8(v.destroy_into_vec())?)
     }
 
     fn try_read(buf: &mut &[u8]) -> FFIResult<String> {
@@ -106,120 +296,927 @@ unsafe impl<UT> FFIConverter<UT> for String {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_STRING);
 }
 
-unsafe impl<UT, T: Lower<UT>> Lower<UT> for Option<T> {
-    type FFIType = FFIBuffer;
-    fn lower(obj: Option<T>) -> FFIBuffer {
-        Self::lower_into_buffer(obj)
+fn read_utf16(mut buf: &[u8]) -> FFIResult<String> {
+    if !buf.len().is_multiple_of(2) {
+        bail!("odd number of bytes for Utf16String ({})", buf.len());
     }
-    fn write(obj: Option<T>, buf: &mut Vec<u8>) {
-        match obj {
-            None => buf.put_i8(0),
-            Some(v) => {
-                buf.put_i8(1);
-                T::write(v, buf);
-            }
-        }
+    let mut units = Vec::with_capacity(buf.len() / 2);
+    while buf.has_remaining() {
+        units.push(buf.get_u16());
     }
+    Ok(String::from_utf16(&units)?)
 }
 
-unsafe impl<UT, T: Lift<UT>> Lift<UT> for Option<T> {
+// The lowered buffer holds the bare code units, inside buffers they are prefixed by the unit count.
+unsafe impl<UT> FFIConverter<UT> for Utf16String {
     type FFIType = FFIBuffer;
-    fn try_lift(buf: FFIBuffer) -> FFIResult<Option<T>> {
-        Self::try_lift_from_buffer(buf)
+
+    fn lower(obj: Utf16String) -> Self::FFIType {
+        let mut buf = Vec::with_capacity(obj.len() * 2);
+        for unit in obj.encode_utf16() {
+            buf.put_u16(unit);
+        }
+        FFIBuffer::from_vec(buf)
     }
-    fn try_read(buf: &mut &[u8]) -> FFIResult<Option<T>> {
-        check_remaining(buf, 1)?;
-        Ok(match buf.get_i8() {
-            0 => None,
-            1 => Some(T::try_read(buf)?),
-            _ => bail!("unexpected tag byte for Option"),
-        })
+
+    fn write(obj: Utf16String, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.encode_utf16().count()).unwrap();
+        buf.put_i32(len);
+        for unit in obj.encode_utf16() {
+            buf.put_u16(unit);
+        }
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Utf16String> {
+        read_utf16(&v.destroy_into_vec()).map(Utf16String)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Utf16String> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())? * 2;
+        check_remaining(buf, len)?;
+        let res = read_utf16(&buf.chunk()[..len])?;
+        buf.advance(len);
+        Ok(Utf16String(res))
     }
-}
 
-impl<UT, T: TypeId<UT>> TypeId<UT> for Option<T> {
     const TYPE_ID_META: MetadataBuffer =
-        MetadataBuffer::from_code(metadata::codes::TYPE_OPTION).concat(T::TYPE_ID_META);
+        MetadataBuffer::from_code(metadata::codes::TYPE_UTF16_STRING);
 }
 
-unsafe impl<UT, T: Lower<UT>> Lower<UT> for Vec<T> {
-    type FFIType = FFIBuffer;
-    fn lower(obj: Vec<T>) -> FFIBuffer {
-        Self::lower_into_buffer(obj)
+// Returned as an owned pointer that foreign code frees with `ffihelper_cstring_free`. Borrowed
+// `const char*` arguments are lifted as `CStrArg`, so a direct `CString` argument is passed in a
+// buffer like `String`. Inside buffers the bytes are length-prefixed without the NUL terminator.
+unsafe impl<UT> Lower<UT> for CString {
+    type FFIType = *mut c_char;
+
+    fn lower(obj: CString) -> Self::FFIType {
+        obj.into_raw()
     }
-    fn write(obj: Vec<T>, buf: &mut Vec<u8>) {
-        let len = i32::try_from(obj.len()).unwrap();
+
+    fn write(obj: CString, buf: &mut Vec<u8>) {
+        let bytes = obj.into_bytes();
+        let len = i32::try_from(bytes.len()).unwrap();
         buf.put_i32(len);
-        for item in obj {
-            <T as Lower<UT>>::write(item, buf);
-        }
+        buf.put_slice(&bytes);
     }
 }
 
-unsafe impl<UT, T: Lift<UT>> Lift<UT> for Vec<T> {
+unsafe impl<UT> Lift<UT> for CString {
     type FFIType = FFIBuffer;
-    fn try_lift(buf: FFIBuffer) -> FFIResult<Vec<T>> {
-        Self::try_lift_from_buffer(buf)
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<CString> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
     }
-    fn try_read(buf: &mut &[u8]) -> FFIResult<Vec<T>> {
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<CString> {
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
-        let mut vec = Vec::with_capacity(len);
-        for _ in 0..len {
-            vec.push(<T as Lift<UT>>::try_read(buf)?)
+        check_remaining(buf, len)?;
+        let res = CString::new(&buf.chunk()[..len])?;
+        buf.advance(len);
+        Ok(res)
+    }
+}
+
+impl<UT> TypeId<UT> for CString {
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_C_STRING);
+}
+
+unsafe impl<'a, UT> Lift<UT> for CStrArg<'a> {
+    type FFIType = FFICStr<'a>;
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<CStrArg<'a>> {
+        if v.as_ptr().is_null() {
+            bail!("unexpected null pointer for CStrArg");
         }
-        Ok(vec)
+        Ok(CStrArg(v))
+    }
+
+    fn try_read(_buf: &mut &[u8]) -> FFIResult<CStrArg<'a>> {
+        bail!("CStrArg can only be passed as a direct argument")
     }
 }
 
-impl<UT, T: TypeId<UT>> TypeId<UT> for Vec<T> {
+impl<UT> TypeId<UT> for CStrArg<'_> {
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_C_STR);
+}
+
+// On unix the raw OS bytes are transferred as-is, on windows the UTF-16 code units from
+// `encode_wide`. Other platforms transfer the encoded bytes and only accept UTF-8 back.
+#[cfg(not(windows))]
+const OS_STRING_ENCODING: u8 = metadata::codes::OS_STRING_BYTES;
+
+#[cfg(windows)]
+const OS_STRING_ENCODING: u8 = metadata::codes::OS_STRING_UTF16;
+
+#[cfg(unix)]
+fn os_string_into_vec(s: OsString) -> Vec<u8> {
+    std::os::unix::ffi::OsStringExt::into_vec(s)
+}
+
+#[cfg(windows)]
+fn os_string_into_vec(s: OsString) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(s.len() * 2);
+    for unit in std::os::windows::ffi::OsStrExt::encode_wide(s.as_os_str()) {
+        buf.put_u16(unit);
+    }
+    buf
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_string_into_vec(s: OsString) -> Vec<u8> {
+    s.into_encoded_bytes()
+}
+
+#[cfg(unix)]
+fn os_string_from_vec(v: Vec<u8>) -> FFIResult<OsString> {
+    Ok(std::os::unix::ffi::OsStringExt::from_vec(v))
+}
+
+#[cfg(windows)]
+fn os_string_from_vec(v: Vec<u8>) -> FFIResult<OsString> {
+    if !v.len().is_multiple_of(2) {
+        bail!("odd number of bytes for OsString ({})", v.len());
+    }
+    let mut buf = v.as_slice();
+    let mut units = Vec::with_capacity(v.len() / 2);
+    while buf.has_remaining() {
+        units.push(buf.get_u16());
+    }
+    Ok(std::os::windows::ffi::OsStringExt::from_wide(&units))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn os_string_from_vec(v: Vec<u8>) -> FFIResult<OsString> {
+    Ok(OsString::from(String::from_utf8(v)?))
+}
+
+unsafe impl<UT> FFIConverter<UT> for OsString {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: OsString) -> Self::FFIType {
+        FFIBuffer::from_vec(os_string_into_vec(obj))
+    }
+
+    fn write(obj: OsString, buf: &mut Vec<u8>) {
+        let bytes = os_string_into_vec(obj);
+        let len = i32::try_from(bytes.len()).unwrap();
+        buf.put_i32(len);
+        buf.put_slice(&bytes);
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<OsString> {
+        os_string_from_vec(v.destroy_into_vec())
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<OsString> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        let res = os_string_from_vec(buf.chunk()[..len].to_vec())?;
+        buf.advance(len);
+        Ok(res)
+    }
+
     const TYPE_ID_META: MetadataBuffer =
-        MetadataBuffer::from_code(metadata::codes::TYPE_VEC).concat(T::TYPE_ID_META);
+        MetadataBuffer::from_code(metadata::codes::TYPE_OS_STRING).concat_value(OS_STRING_ENCODING);
 }
 
-unsafe impl<K, V, UT> Lower<UT> for HashMap<K, V>
-where
-    K: Lower<UT> + std::hash::Hash + Eq,
-    V: Lower<UT>,
-{
+unsafe impl<UT> FFIConverter<UT> for PathBuf {
     type FFIType = FFIBuffer;
-    fn lower(obj: HashMap<K, V>) -> FFIBuffer {
-        Self::lower_into_buffer(obj)
+
+    fn lower(obj: PathBuf) -> Self::FFIType {
+        <OsString as FFIConverter<UT>>::lower(obj.into_os_string())
     }
-    fn write(obj: HashMap<K, V>, buf: &mut Vec<u8>) {
+
+    fn write(obj: PathBuf, buf: &mut Vec<u8>) {
+        <OsString as FFIConverter<UT>>::write(obj.into_os_string(), buf)
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<PathBuf> {
+        <OsString as FFIConverter<UT>>::try_lift(v).map(PathBuf::from)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<PathBuf> {
+        <OsString as FFIConverter<UT>>::try_read(buf).map(PathBuf::from)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_PATH_BUF).concat_value(OS_STRING_ENCODING);
+}
+
+unsafe impl<UT> FFIConverter<UT> for Bytes {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: Bytes) -> Self::FFIType {
+        FFIBuffer::from_vec(obj.0)
+    }
+
+    fn write(obj: Bytes, buf: &mut Vec<u8>) {
         let len = i32::try_from(obj.len()).unwrap();
         buf.put_i32(len);
-        for (key, value) in obj {
-            <K as Lower<UT>>::write(key, buf);
-            <V as Lower<UT>>::write(value, buf);
-        }
+        buf.put_slice(&obj);
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Bytes> {
+        Ok(Bytes(v.destroy_into_vec()))
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Bytes> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        let res = buf.chunk()[..len].to_vec();
+        buf.advance(len);
+        Ok(Bytes(res))
     }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BYTES);
 }
 
-unsafe impl<K, V, UT> Lift<UT> for HashMap<K, V>
-where
-    K: Lift<UT> + std::hash::Hash + Eq,
-    V: Lift<UT>,
-{
+unsafe impl<UT> FFIConverter<UT> for bytes::Bytes {
     type FFIType = FFIBuffer;
-    fn try_lift(buf: FFIBuffer) -> FFIResult<HashMap<K, V>> {
-        Self::try_lift_from_buffer(buf)
+
+    fn lower(obj: bytes::Bytes) -> Self::FFIType {
+        FFIBuffer::from_vec(Vec::from(obj))
     }
-    fn try_read(buf: &mut &[u8]) -> FFIResult<HashMap<K, V>> {
+
+    fn write(obj: bytes::Bytes, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put_slice(&obj);
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<bytes::Bytes> {
+        Ok(bytes::Bytes::from(v.destroy_into_vec()))
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<bytes::Bytes> {
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
-        let mut map = HashMap::with_capacity(len);
-        for _ in 0..len {
-            let key = <K as Lift<UT>>::try_read(buf)?;
-            let value = <V as Lift<UT>>::try_read(buf)?;
-            map.insert(key, value);
-        }
-        Ok(map)
+        check_remaining(buf, len)?;
+        Ok(buf.copy_to_bytes(len))
     }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BYTES);
 }
 
-impl<K, V, UT> TypeId<UT> for HashMap<K, V>
-where
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+// Wire layout: u64 seconds followed by u32 nanoseconds.
+unsafe impl<UT> FFIConverter<UT> for Duration {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: Duration) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: Duration, buf: &mut Vec<u8>) {
+        buf.put_u64(obj.as_secs());
+        buf.put_u32(obj.subsec_nanos());
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Duration> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Duration> {
+        check_remaining(buf, 12)?;
+        let secs = buf.get_u64();
+        let nanos = buf.get_u32();
+        if nanos >= NANOS_PER_SEC {
+            bail!("nanoseconds out of range for Duration ({nanos})");
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DURATION);
+}
+
+// Wire layout: i64 seconds relative to the unix epoch (negative before it), followed by u32
+// nanoseconds counted forward from that second.
+unsafe impl<UT> FFIConverter<UT> for SystemTime {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: SystemTime) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: SystemTime, buf: &mut Vec<u8>) {
+        let (secs, nanos) = match obj.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(d) => (i64::try_from(d.as_secs()).unwrap(), d.subsec_nanos()),
+            Err(e) => {
+                // Negate in i128, `i64::MIN` seconds before the epoch doesn't fit in a positive i64.
+                let d = e.duration();
+                let secs = -i128::from(d.as_secs());
+                let (secs, nanos) = match d.subsec_nanos() {
+                    0 => (secs, 0),
+                    n => (secs - 1, NANOS_PER_SEC - n),
+                };
+                (i64::try_from(secs).unwrap(), nanos)
+            }
+        };
+        buf.put_i64(secs);
+        buf.put_u32(nanos);
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<SystemTime> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<SystemTime> {
+        check_remaining(buf, 12)?;
+        let secs = buf.get_i64();
+        let nanos = buf.get_u32();
+        if nanos >= NANOS_PER_SEC {
+            bail!("nanoseconds out of range for SystemTime ({nanos})");
+        }
+        let time = if secs >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+        } else {
+            SystemTime::UNIX_EPOCH
+                .checked_sub(Duration::from_secs(secs.unsigned_abs()))
+                .and_then(|t| t.checked_add(Duration::from_nanos(u64::from(nanos))))
+        };
+        match time {
+            Some(t) => Ok(t),
+            None => bail!("timestamp out of range for SystemTime ({secs}s, {nanos}ns)"),
+        }
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
+}
+
+unsafe impl<UT> FFIConverter<UT> for Ipv4Addr {
+    type FFIType = u32;
+
+    fn lower(obj: Ipv4Addr) -> Self::FFIType {
+        u32::from(obj)
+    }
+
+    fn write(obj: Ipv4Addr, buf: &mut Vec<u8>) {
+        buf.put_slice(&obj.octets());
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Ipv4Addr> {
+        Ok(Ipv4Addr::from(v))
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Ipv4Addr> {
+        check_remaining(buf, 4)?;
+        let mut octets = [0u8; 4];
+        buf.copy_to_slice(&mut octets);
+        Ok(Ipv4Addr::from(octets))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_IPV4_ADDR);
+}
+
+unsafe impl<UT> FFIConverter<UT> for Ipv6Addr {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: Ipv6Addr) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: Ipv6Addr, buf: &mut Vec<u8>) {
+        buf.put_slice(&obj.octets());
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Ipv6Addr> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Ipv6Addr> {
+        check_remaining(buf, 16)?;
+        let mut octets = [0u8; 16];
+        buf.copy_to_slice(&mut octets);
+        Ok(Ipv6Addr::from(octets))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_IPV6_ADDR);
+}
+
+unsafe impl<UT> FFIConverter<UT> for IpAddr {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: IpAddr) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: IpAddr, buf: &mut Vec<u8>) {
+        match obj {
+            IpAddr::V4(addr) => {
+                buf.put_i8(4);
+                <Ipv4Addr as FFIConverter<UT>>::write(addr, buf);
+            }
+            IpAddr::V6(addr) => {
+                buf.put_i8(6);
+                <Ipv6Addr as FFIConverter<UT>>::write(addr, buf);
+            }
+        }
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<IpAddr> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<IpAddr> {
+        check_remaining(buf, 1)?;
+        Ok(match buf.get_i8() {
+            4 => IpAddr::V4(<Ipv4Addr as FFIConverter<UT>>::try_read(buf)?),
+            6 => IpAddr::V6(<Ipv6Addr as FFIConverter<UT>>::try_read(buf)?),
+            _ => bail!("unexpected tag byte for IpAddr"),
+        })
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_IP_ADDR);
+}
+
+unsafe impl<UT> FFIConverter<UT> for SocketAddrV4 {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: SocketAddrV4) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: SocketAddrV4, buf: &mut Vec<u8>) {
+        <Ipv4Addr as FFIConverter<UT>>::write(*obj.ip(), buf);
+        buf.put_u16(obj.port());
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<SocketAddrV4> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<SocketAddrV4> {
+        let ip = <Ipv4Addr as FFIConverter<UT>>::try_read(buf)?;
+        check_remaining(buf, 2)?;
+        Ok(SocketAddrV4::new(ip, buf.get_u16()))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SOCKET_ADDR_V4);
+}
+
+unsafe impl<UT> FFIConverter<UT> for SocketAddrV6 {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: SocketAddrV6) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: SocketAddrV6, buf: &mut Vec<u8>) {
+        <Ipv6Addr as FFIConverter<UT>>::write(*obj.ip(), buf);
+        buf.put_u16(obj.port());
+        buf.put_u32(obj.flowinfo());
+        buf.put_u32(obj.scope_id());
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<SocketAddrV6> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<SocketAddrV6> {
+        let ip = <Ipv6Addr as FFIConverter<UT>>::try_read(buf)?;
+        check_remaining(buf, 10)?;
+        let port = buf.get_u16();
+        let flowinfo = buf.get_u32();
+        let scope_id = buf.get_u32();
+        Ok(SocketAddrV6::new(ip, port, flowinfo, scope_id))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SOCKET_ADDR_V6);
+}
+
+unsafe impl<UT> FFIConverter<UT> for SocketAddr {
+    type FFIType = FFIBuffer;
+
+    fn lower(obj: SocketAddr) -> Self::FFIType {
+        <Self as Lower<UT>>::lower_into_buffer(obj)
+    }
+
+    fn write(obj: SocketAddr, buf: &mut Vec<u8>) {
+        match obj {
+            SocketAddr::V4(addr) => {
+                buf.put_i8(4);
+                <SocketAddrV4 as FFIConverter<UT>>::write(addr, buf);
+            }
+            SocketAddr::V6(addr) => {
+                buf.put_i8(6);
+                <SocketAddrV6 as FFIConverter<UT>>::write(addr, buf);
+            }
+        }
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<SocketAddr> {
+        <Self as Lift<UT>>::try_lift_from_buffer(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<SocketAddr> {
+        check_remaining(buf, 1)?;
+        Ok(match buf.get_i8() {
+            4 => SocketAddr::V4(<SocketAddrV4 as FFIConverter<UT>>::try_read(buf)?),
+            6 => SocketAddr::V6(<SocketAddrV6 as FFIConverter<UT>>::try_read(buf)?),
+            _ => bail!("unexpected tag byte for SocketAddr"),
+        })
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SOCKET_ADDR);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Option<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: Option<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: Option<T>, buf: &mut Vec<u8>) {
+        match obj {
+            None => buf.put_i8(0),
+            Some(v) => {
+                buf.put_i8(1);
+                T::write(v, buf);
+            }
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Option<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<Option<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Option<T>> {
+        check_remaining(buf, 1)?;
+        Ok(match buf.get_i8() {
+            0 => None,
+            1 => Some(T::try_read(buf)?),
+            _ => bail!("unexpected tag byte for Option"),
+        })
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for Option<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_OPTION).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Box<T> {
+    type FFIType = T::FFIType;
+    fn lower(obj: Box<T>) -> Self::FFIType {
+        T::lower(*obj)
+    }
+    fn write(obj: Box<T>, buf: &mut Vec<u8>) {
+        T::write(*obj, buf)
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Box<T> {
+    type FFIType = T::FFIType;
+    fn try_lift(v: Self::FFIType) -> FFIResult<Box<T>> {
+        T::try_lift(v).map(Box::new)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Box<T>> {
+        T::try_read(buf).map(Box::new)
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for Box<T> {
+    const TYPE_ID_META: MetadataBuffer = T::TYPE_ID_META;
+}
+
+unsafe impl<UT, T: FFIObject + TypeId<UT> + ?Sized> FFIConverter<UT> for Arc<T> {
+    type FFIType = <T::Handle as ObjectHandle<T>>::FFIType;
+
+    fn lower(obj: Arc<T>) -> Self::FFIType {
+        T::Handle::lower(obj)
+    }
+
+    fn write(obj: Arc<T>, buf: &mut Vec<u8>) {
+        T::Handle::write(obj, buf)
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Arc<T>> {
+        T::Handle::try_lift(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Arc<T>> {
+        T::Handle::try_read(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <T as TypeId<UT>>::TYPE_ID_META;
+}
+
+derive_ffi_traits!(impl<UT, T> Lower<UT> for Arc<T> where T: FFIObject + TypeId<UT> + ?Sized);
+derive_ffi_traits!(impl<UT, T> Lift<UT> for Arc<T> where T: FFIObject + TypeId<UT> + ?Sized);
+derive_ffi_traits!(impl<UT, T> TypeId<UT> for Arc<T> where T: FFIObject + TypeId<UT> + ?Sized);
+
+unsafe impl<UT, T: Lower<UT>, E: Lower<UT>> Lower<UT> for Result<T, E> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: Result<T, E>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: Result<T, E>, buf: &mut Vec<u8>) {
+        match obj {
+            Ok(v) => {
+                buf.put_i8(0);
+                T::write(v, buf);
+            }
+            Err(e) => {
+                buf.put_i8(1);
+                E::write(e, buf);
+            }
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>, E: Lift<UT>> Lift<UT> for Result<T, E> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<Result<T, E>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Result<T, E>> {
+        check_remaining(buf, 1)?;
+        Ok(match buf.get_i8() {
+            0 => Ok(T::try_read(buf)?),
+            1 => Err(E::try_read(buf)?),
+            _ => bail!("unexpected tag byte for Result"),
+        })
+    }
+}
+
+impl<UT, T: TypeId<UT>, E: TypeId<UT>> TypeId<UT> for Result<T, E> {
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_RESULT)
+        .concat(T::TYPE_ID_META)
+        .concat(E::TYPE_ID_META);
+}
+
+// `start > end` is false for NaN bounds, so anything that isn't ordered before `end` is rejected.
+fn check_range_order<T: PartialOrd>(start: &T, end: &T) -> FFIResult<()> {
+    match start.partial_cmp(end) {
+        Some(Ordering::Less | Ordering::Equal) => Ok(()),
+        Some(Ordering::Greater) => bail!("range start is greater than its end"),
+        None => bail!("range bounds are not comparable"),
+    }
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Range<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: Range<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: Range<T>, buf: &mut Vec<u8>) {
+        T::write(obj.start, buf);
+        T::write(obj.end, buf);
+    }
+}
+
+unsafe impl<UT, T: Lift<UT> + PartialOrd> Lift<UT> for Range<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<Range<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Range<T>> {
+        let start = T::try_read(buf)?;
+        let end = T::try_read(buf)?;
+        check_range_order(&start, &end)?;
+        Ok(start..end)
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for Range<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_RANGE).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for RangeInclusive<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: RangeInclusive<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: RangeInclusive<T>, buf: &mut Vec<u8>) {
+        let (start, end) = obj.into_inner();
+        T::write(start, buf);
+        T::write(end, buf);
+    }
+}
+
+unsafe impl<UT, T: Lift<UT> + PartialOrd> Lift<UT> for RangeInclusive<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<RangeInclusive<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<RangeInclusive<T>> {
+        let start = T::try_read(buf)?;
+        let end = T::try_read(buf)?;
+        check_range_order(&start, &end)?;
+        Ok(start..=end)
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for RangeInclusive<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_RANGE_INCLUSIVE).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Bound<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: Bound<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: Bound<T>, buf: &mut Vec<u8>) {
+        match obj {
+            Bound::Included(v) => {
+                buf.put_i8(0);
+                T::write(v, buf);
+            }
+            Bound::Excluded(v) => {
+                buf.put_i8(1);
+                T::write(v, buf);
+            }
+            Bound::Unbounded => buf.put_i8(2),
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Bound<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<Bound<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Bound<T>> {
+        check_remaining(buf, 1)?;
+        Ok(match buf.get_i8() {
+            0 => Bound::Included(T::try_read(buf)?),
+            1 => Bound::Excluded(T::try_read(buf)?),
+            2 => Bound::Unbounded,
+            _ => bail!("unexpected tag byte for Bound"),
+        })
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for Bound<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_BOUND).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for BoundRange<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: BoundRange<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: BoundRange<T>, buf: &mut Vec<u8>) {
+        <Bound<T> as Lower<UT>>::write(obj.start, buf);
+        <Bound<T> as Lower<UT>>::write(obj.end, buf);
+    }
+}
+
+unsafe impl<UT, T: Lift<UT> + PartialOrd> Lift<UT> for BoundRange<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<BoundRange<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<BoundRange<T>> {
+        let start = <Bound<T> as Lift<UT>>::try_read(buf)?;
+        let end = <Bound<T> as Lift<UT>>::try_read(buf)?;
+        if let (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) =
+            (&start, &end)
+        {
+            check_range_order(s, e)?;
+        }
+        Ok(BoundRange { start, end })
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for BoundRange<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_BOUND_RANGE).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Vec<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: Vec<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: Vec<T>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Vec<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<Vec<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Vec<T>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut vec = Vec::with_capacity(len);
+        for _ in 0..len {
+            vec.push(<T as Lift<UT>>::try_read(buf)?)
+        }
+        Ok(vec)
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for Vec<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_VEC).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>, const N: usize> Lower<UT> for [T; N] {
+    type FFIType = FFIBuffer;
+    fn lower(obj: [T; N]) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: [T; N], buf: &mut Vec<u8>) {
+        let len = i32::try_from(N).unwrap();
+        buf.put_i32(len);
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>, const N: usize> Lift<UT> for [T; N] {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<[T; N]> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<[T; N]> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        if len != N {
+            bail!("unexpected length for array (expected {N}, got {len})");
+        }
+        let mut vec = Vec::with_capacity(N);
+        for _ in 0..N {
+            vec.push(<T as Lift<UT>>::try_read(buf)?)
+        }
+        match vec.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl<UT, T: TypeId<UT>, const N: usize> TypeId<UT> for [T; N] {
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_ARRAY)
+        .concat_u32(N as u32)
+        .concat(T::TYPE_ID_META);
+}
+
+unsafe impl<K, V, UT> Lower<UT> for HashMap<K, V>
+where
+    K: Lower<UT> + std::hash::Hash + Eq,
+    V: Lower<UT>,
+{
+    type FFIType = FFIBuffer;
+    fn lower(obj: HashMap<K, V>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: HashMap<K, V>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        for (key, value) in obj {
+            <K as Lower<UT>>::write(key, buf);
+            <V as Lower<UT>>::write(value, buf);
+        }
+    }
+}
+
+unsafe impl<K, V, UT> Lift<UT> for HashMap<K, V>
+where
+    K: Lift<UT> + std::hash::Hash + Eq,
+    V: Lift<UT>,
+{
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<HashMap<K, V>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<HashMap<K, V>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut map = HashMap::with_capacity(len);
+        for _ in 0..len {
+            let key = <K as Lift<UT>>::try_read(buf)?;
+            let value = <V as Lift<UT>>::try_read(buf)?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V, UT> TypeId<UT> for HashMap<K, V>
+where
     K: TypeId<UT> + std::hash::Hash + Eq,
     V: TypeId<UT>,
 {
@@ -228,6 +1225,207 @@ where
         .concat(V::TYPE_ID_META);
 }
 
+unsafe impl<K, V, UT> Lower<UT> for BTreeMap<K, V>
+where
+    K: Lower<UT> + Ord,
+    V: Lower<UT>,
+{
+    type FFIType = FFIBuffer;
+    fn lower(obj: BTreeMap<K, V>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: BTreeMap<K, V>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        for (key, value) in obj {
+            <K as Lower<UT>>::write(key, buf);
+            <V as Lower<UT>>::write(value, buf);
+        }
+    }
+}
+
+unsafe impl<K, V, UT> Lift<UT> for BTreeMap<K, V>
+where
+    K: Lift<UT> + Ord,
+    V: Lift<UT>,
+{
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<BTreeMap<K, V>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<BTreeMap<K, V>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            let key = <K as Lift<UT>>::try_read(buf)?;
+            let value = <V as Lift<UT>>::try_read(buf)?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V, UT> TypeId<UT> for BTreeMap<K, V>
+where
+    K: TypeId<UT> + Ord,
+    V: TypeId<UT>,
+{
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BTREE_MAP)
+        .concat(K::TYPE_ID_META)
+        .concat(V::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT> + Ord> Lower<UT> for BTreeSet<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: BTreeSet<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: BTreeSet<T>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT> + Ord> Lift<UT> for BTreeSet<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<BTreeSet<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<BTreeSet<T>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut set = BTreeSet::new();
+        for _ in 0..len {
+            set.insert(<T as Lift<UT>>::try_read(buf)?);
+        }
+        Ok(set)
+    }
+}
+
+impl<UT, T: TypeId<UT> + Ord> TypeId<UT> for BTreeSet<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_BTREE_SET).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT> + std::hash::Hash + Eq> Lower<UT> for HashSet<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: HashSet<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: HashSet<T>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT> + std::hash::Hash + Eq> Lift<UT> for HashSet<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<HashSet<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<HashSet<T>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut set = HashSet::with_capacity(len);
+        for _ in 0..len {
+            set.insert(<T as Lift<UT>>::try_read(buf)?);
+        }
+        Ok(set)
+    }
+}
+
+impl<UT, T: TypeId<UT> + std::hash::Hash + Eq> TypeId<UT> for HashSet<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_HASH_SET).concat(T::TYPE_ID_META);
+}
+
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for VecDeque<T> {
+    type FFIType = FFIBuffer;
+    fn lower(obj: VecDeque<T>) -> FFIBuffer {
+        Self::lower_into_buffer(obj)
+    }
+    fn write(obj: VecDeque<T>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        for item in obj {
+            <T as Lower<UT>>::write(item, buf);
+        }
+    }
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for VecDeque<T> {
+    type FFIType = FFIBuffer;
+    fn try_lift(buf: FFIBuffer) -> FFIResult<VecDeque<T>> {
+        Self::try_lift_from_buffer(buf)
+    }
+    fn try_read(buf: &mut &[u8]) -> FFIResult<VecDeque<T>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut deque = VecDeque::with_capacity(len);
+        for _ in 0..len {
+            deque.push_back(<T as Lift<UT>>::try_read(buf)?)
+        }
+        Ok(deque)
+    }
+}
+
+impl<UT, T: TypeId<UT>> TypeId<UT> for VecDeque<T> {
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_VEC_DEQUE).concat(T::TYPE_ID_META);
+}
+
+macro_rules! impl_ffi_converter_for_tuple {
+    ($len:expr; $($T:ident),+) => {
+        unsafe impl<UT, $($T: Lower<UT>),+> Lower<UT> for ($($T,)+) {
+            type FFIType = FFIBuffer;
+            fn lower(obj: ($($T,)+)) -> FFIBuffer {
+                Self::lower_into_buffer(obj)
+            }
+            #[allow(non_snake_case)]
+            fn write(obj: ($($T,)+), buf: &mut Vec<u8>) {
+                let ($($T,)+) = obj;
+                $(<$T as Lower<UT>>::write($T, buf);)+
+            }
+        }
+
+        unsafe impl<UT, $($T: Lift<UT>),+> Lift<UT> for ($($T,)+) {
+            type FFIType = FFIBuffer;
+            fn try_lift(buf: FFIBuffer) -> FFIResult<($($T,)+)> {
+                Self::try_lift_from_buffer(buf)
+            }
+            fn try_read(buf: &mut &[u8]) -> FFIResult<($($T,)+)> {
+                Ok(($(<$T as Lift<UT>>::try_read(buf)?,)+))
+            }
+        }
+
+        impl<UT, $($T: TypeId<UT>),+> TypeId<UT> for ($($T,)+) {
+            const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_TUPLE)
+                .concat_value($len)
+                $(.concat($T::TYPE_ID_META))+;
+        }
+    };
+}
+
+impl_ffi_converter_for_tuple!(1; A);
+impl_ffi_converter_for_tuple!(2; A, B);
+impl_ffi_converter_for_tuple!(3; A, B, C);
+impl_ffi_converter_for_tuple!(4; A, B, C, D);
+impl_ffi_converter_for_tuple!(5; A, B, C, D, E);
+impl_ffi_converter_for_tuple!(6; A, B, C, D, E, F);
+impl_ffi_converter_for_tuple!(7; A, B, C, D, E, F, G);
+impl_ffi_converter_for_tuple!(8; A, B, C, D, E, F, G, H);
+impl_ffi_converter_for_tuple!(9; A, B, C, D, E, F, G, H, I);
+impl_ffi_converter_for_tuple!(10; A, B, C, D, E, F, G, H, I, J);
+impl_ffi_converter_for_tuple!(11; A, B, C, D, E, F, G, H, I, J, K);
+impl_ffi_converter_for_tuple!(12; A, B, C, D, E, F, G, H, I, J, K, L);
+
 derive_ffi_traits!(blanket u8);
 derive_ffi_traits!(blanket i8);
 derive_ffi_traits!(blanket u16);
@@ -238,5 +1436,36 @@ derive_ffi_traits!(blanket u64);
 derive_ffi_traits!(blanket i64);
 derive_ffi_traits!(blanket f32);
 derive_ffi_traits!(blanket f64);
+derive_ffi_traits!(blanket u128);
+derive_ffi_traits!(blanket i128);
+derive_ffi_traits!(blanket usize);
+derive_ffi_traits!(blanket isize);
+derive_ffi_traits!(blanket NonZeroU8);
+derive_ffi_traits!(blanket NonZeroI8);
+derive_ffi_traits!(blanket NonZeroU16);
+derive_ffi_traits!(blanket NonZeroI16);
+derive_ffi_traits!(blanket NonZeroU32);
+derive_ffi_traits!(blanket NonZeroI32);
+derive_ffi_traits!(blanket NonZeroU64);
+derive_ffi_traits!(blanket NonZeroI64);
+derive_ffi_traits!(blanket NonZeroU128);
+derive_ffi_traits!(blanket NonZeroI128);
+derive_ffi_traits!(blanket NonZeroUsize);
+derive_ffi_traits!(blanket NonZeroIsize);
+derive_ffi_traits!(blanket());
 derive_ffi_traits!(blanket bool);
+derive_ffi_traits!(blanket char);
 derive_ffi_traits!(blanket String);
+derive_ffi_traits!(blanket Utf16String);
+derive_ffi_traits!(blanket OsString);
+derive_ffi_traits!(blanket PathBuf);
+derive_ffi_traits!(blanket Bytes);
+derive_ffi_traits!(blanket bytes::Bytes);
+derive_ffi_traits!(blanket Duration);
+derive_ffi_traits!(blanket SystemTime);
+derive_ffi_traits!(blanket Ipv4Addr);
+derive_ffi_traits!(blanket Ipv6Addr);
+derive_ffi_traits!(blanket IpAddr);
+derive_ffi_traits!(blanket SocketAddrV4);
+derive_ffi_traits!(blanket SocketAddrV6);
+derive_ffi_traits!(blanket SocketAddr);
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::bail;
 use bytes::Buf;
 
@@ -55,6 +57,39 @@ pub trait TypeId<UT> {
     const TYPE_ID_META: MetadataBuffer;
 }
 
+/// Type passed to foreign code as an opaque `Arc<Self>` handle, see [crate::ffi_object].
+///
+/// The [TypeId] of the object itself describes `Arc<Self>`, so interfaces can list their methods.
+pub trait FFIObject: Send + Sync + 'static {
+    const NAME: &'static str;
+
+    /// How `Arc<Self>` is represented on the foreign side
+    type Handle: ObjectHandle<Self>;
+}
+
+/// Converts `Arc<T>` to and from the handle passed to foreign code.
+///
+/// # Safety
+///
+/// `try_lift` and `try_read` must only return objects previously handed out by `lower` or `write`.
+pub unsafe trait ObjectHandle<T: ?Sized> {
+    type FFIType: FFIDefault;
+    /// Recorded in the metadata so bindings know how to release the handle, one of the
+    /// `OBJECT_HANDLE_*` codes
+    const KIND: u8;
+
+    fn lower(obj: Arc<T>) -> Self::FFIType;
+    fn write(obj: Arc<T>, buf: &mut Vec<u8>);
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Arc<T>>;
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Arc<T>>;
+}
+
+pub trait LowerError<UT>: Sized {
+    /// Lower the error into the buffer stored in [crate::ffi::call::FFIErrStatus::error]
+    fn lower_error(obj: Self) -> FFIBuffer;
+}
+
 #[macro_export]
 #[allow(clippy::crate_in_macro_def)]
 macro_rules! derive_ffi_traits {
@@ -99,3 +134,245 @@ macro_rules! derive_ffi_traits {
         }
     };
 }
+
+/// Implements the FFI traits for a type that is converted to and from a builtin type.
+///
+/// `try_into` converts the builtin value into the custom type and `from` converts the custom type
+/// back into the builtin value. They default to `TryFrom<builtin>` and `From<custom>` respectively.
+#[macro_export]
+macro_rules! custom_type {
+    ($ty:ident, $builtin:ty $(,)?) => {
+        $crate::custom_type!(
+            $ty,
+            $builtin,
+            try_into = |v: $builtin| Ok(<$ty as ::std::convert::TryFrom<$builtin>>::try_from(v)?),
+            from = |obj: $ty| <$builtin as ::std::convert::From<$ty>>::from(obj)
+        );
+    };
+
+    ($ty:ident, $builtin:ty, try_into = $try_into:expr $(,)?) => {
+        $crate::custom_type!(
+            $ty,
+            $builtin,
+            try_into = $try_into,
+            from = |obj: $ty| <$builtin as ::std::convert::From<$ty>>::from(obj)
+        );
+    };
+
+    ($ty:ident, $builtin:ty, from = $from:expr $(,)?) => {
+        $crate::custom_type!(
+            $ty,
+            $builtin,
+            try_into = |v: $builtin| Ok(<$ty as ::std::convert::TryFrom<$builtin>>::try_from(v)?),
+            from = $from
+        );
+    };
+
+    ($ty:ident, $builtin:ty, try_into = $try_into:expr, from = $from:expr $(,)?) => {
+        unsafe impl<UT> $crate::Lower<UT> for $ty {
+            type FFIType = <$builtin as $crate::Lower<UT>>::FFIType;
+            fn lower(obj: Self) -> Self::FFIType {
+                let from: fn($ty) -> $builtin = $from;
+                <$builtin as $crate::Lower<UT>>::lower(from(obj))
+            }
+            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+                let from: fn($ty) -> $builtin = $from;
+                <$builtin as $crate::Lower<UT>>::write(from(obj), buf)
+            }
+        }
+
+        unsafe impl<UT> $crate::Lift<UT> for $ty {
+            type FFIType = <$builtin as $crate::Lift<UT>>::FFIType;
+            fn try_lift(v: Self::FFIType) -> $crate::FFIResult<Self> {
+                let try_into: fn($builtin) -> $crate::FFIResult<$ty> = $try_into;
+                try_into(<$builtin as $crate::Lift<UT>>::try_lift(v)?)
+            }
+            fn try_read(buf: &mut &[u8]) -> $crate::FFIResult<Self> {
+                let try_into: fn($builtin) -> $crate::FFIResult<$ty> = $try_into;
+                try_into(<$builtin as $crate::Lift<UT>>::try_read(buf)?)
+            }
+        }
+
+        impl<UT> $crate::TypeId<UT> for $ty {
+            const TYPE_ID_META: $crate::metadata::MetadataBuffer =
+                $crate::metadata::MetadataBuffer::from_code($crate::metadata::codes::TYPE_CUSTOM)
+                    .concat_str(stringify!($ty))
+                    .concat(<$builtin as $crate::TypeId<UT>>::TYPE_ID_META);
+        }
+    };
+}
+
+/// Implements the FFI traits for a bitflags-style type, passing it as its `$repr` bits.
+///
+/// The type must provide `bits`, `from_bits`, `from_bits_truncate` and `from_bits_retain`, as
+/// generated by the `bitflags` crate. `unknown_bits` picks how lifting treats bits that do not
+/// belong to any listed flag: `reject` fails, `truncate` drops them and `retain` keeps them.
+#[macro_export]
+macro_rules! ffi_flags {
+    ($ty:ident, $repr:ty, unknown_bits = $policy:ident, [$($flag:ident),* $(,)?] $(,)?) => {
+        unsafe impl<UT> $crate::Lower<UT> for $ty {
+            type FFIType = <$repr as $crate::Lower<UT>>::FFIType;
+            fn lower(obj: Self) -> Self::FFIType {
+                <$repr as $crate::Lower<UT>>::lower(obj.bits())
+            }
+            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+                <$repr as $crate::Lower<UT>>::write(obj.bits(), buf)
+            }
+        }
+
+        unsafe impl<UT> $crate::Lift<UT> for $ty {
+            type FFIType = <$repr as $crate::Lift<UT>>::FFIType;
+            fn try_lift(v: Self::FFIType) -> $crate::FFIResult<Self> {
+                $crate::ffi_flags!(@from_bits $policy, $ty, <$repr as $crate::Lift<UT>>::try_lift(v)?)
+            }
+            fn try_read(buf: &mut &[u8]) -> $crate::FFIResult<Self> {
+                $crate::ffi_flags!(@from_bits $policy, $ty, <$repr as $crate::Lift<UT>>::try_read(buf)?)
+            }
+        }
+
+        impl<UT> $crate::TypeId<UT> for $ty {
+            const TYPE_ID_META: $crate::metadata::MetadataBuffer =
+                $crate::metadata::MetadataBuffer::from_code($crate::metadata::codes::TYPE_FLAGS)
+                    .concat_str(stringify!($ty))
+                    .concat(<$repr as $crate::TypeId<UT>>::TYPE_ID_META)
+                    .concat_value($crate::ffi_flags!(@policy_code $policy))
+                    .concat_value([$(stringify!($flag)),*].len() as u8)
+                    $(.concat_str(stringify!($flag)).concat_u64(<$ty>::$flag.bits() as u64))*;
+        }
+    };
+
+    (@from_bits reject, $ty:ident, $bits:expr) => {
+        match <$ty>::from_bits($bits) {
+            Some(v) => Ok(v),
+            None => $crate::anyhow::bail!(concat!("unknown bits set for ", stringify!($ty))),
+        }
+    };
+    (@from_bits truncate, $ty:ident, $bits:expr) => {
+        Ok(<$ty>::from_bits_truncate($bits))
+    };
+    (@from_bits retain, $ty:ident, $bits:expr) => {
+        Ok(<$ty>::from_bits_retain($bits))
+    };
+
+    (@policy_code reject) => {
+        $crate::metadata::codes::UNKNOWN_BITS_REJECT
+    };
+    (@policy_code truncate) => {
+        $crate::metadata::codes::UNKNOWN_BITS_TRUNCATE
+    };
+    (@policy_code retain) => {
+        $crate::metadata::codes::UNKNOWN_BITS_RETAIN
+    };
+}
+
+/// Implements [FFIObject] for `$ty` and exports `clone_<ty>` and `free_<ty>` functions that
+/// foreign code uses to manage its references to `Arc<$ty>` handles.
+///
+/// Handles are raw pointers by default, which can only be passed as direct arguments and return
+/// values, not inside buffers. With `handle_map` they are checked `u64` handles into a
+/// [crate::handle::HandleMap], and the exported functions report invalid handles through an
+/// [crate::ffi::call::FFIErrStatus] instead of invoking undefined behavior. Trait objects
+/// (`dyn Trait, handle_map`) always use a handle map, see [crate::ffi_trait].
+///
+/// The exported functions are `#[no_mangle]` and named after the bare type name, so two objects
+/// with the same name can't be exported from one library even if they live in different modules.
+#[macro_export]
+macro_rules! ffi_object {
+    ($ty:ident, handle_map) => {
+        $crate::ffi_object!(@mapped $ty, $ty);
+        $crate::ffi_object!(@type_id $ty);
+    };
+
+    (dyn $trait_:ident, handle_map) => {
+        $crate::ffi_object!(@mapped dyn $trait_, $trait_);
+    };
+
+    (@mapped $ty:ty, $name:ident) => {
+        impl $crate::FFIObject for $ty {
+            const NAME: &'static str = stringify!($name);
+            type Handle = $crate::handle::MappedHandle;
+        }
+
+        impl $crate::handle::MappedObject for $ty {
+            fn handle_map() -> &'static $crate::handle::HandleMap<::std::sync::Arc<Self>> {
+                static HANDLE_MAP: $crate::handle::HandleMap<::std::sync::Arc<$ty>> =
+                    $crate::handle::HandleMap::for_type(concat!(
+                        module_path!(),
+                        "::",
+                        stringify!($name)
+                    ));
+                &HANDLE_MAP
+            }
+        }
+
+        $crate::paste::paste! {
+            #[no_mangle]
+            pub extern "C" fn [<clone_ $name:snake>](
+                handle: u64,
+                status: &mut $crate::ffi::call::FFIErrStatus,
+            ) -> u64 {
+                let map = <$ty as $crate::handle::MappedObject>::handle_map();
+                match map.clone_handle(handle) {
+                    Ok(handle) => handle,
+                    Err(e) => {
+                        status.set_unexpected_error(e);
+                        0
+                    }
+                }
+            }
+
+            #[no_mangle]
+            pub extern "C" fn [<free_ $name:snake>](
+                handle: u64,
+                status: &mut $crate::ffi::call::FFIErrStatus,
+            ) {
+                let map = <$ty as $crate::handle::MappedObject>::handle_map();
+                if let Err(e) = map.remove(handle) {
+                    status.set_unexpected_error(e);
+                }
+            }
+        }
+    };
+
+    (@type_id $ty:ident) => {
+        impl<UT> $crate::TypeId<UT> for $ty {
+            const TYPE_ID_META: $crate::metadata::MetadataBuffer =
+                $crate::metadata::MetadataBuffer::from_code($crate::metadata::codes::TYPE_OBJECT)
+                    .concat_str(<$ty as $crate::FFIObject>::NAME)
+                    .concat_value(<<$ty as $crate::FFIObject>::Handle as $crate::ObjectHandle<$ty>>::KIND);
+        }
+    };
+
+    ($ty:ident) => {
+        impl $crate::FFIObject for $ty {
+            const NAME: &'static str = stringify!($ty);
+            type Handle = $crate::handle::RawHandle;
+        }
+
+        $crate::ffi_object!(@type_id $ty);
+
+        $crate::paste::paste! {
+            /// # Safety
+            ///
+            /// `ptr` must be a live handle previously returned to foreign code.
+            #[no_mangle]
+            pub unsafe extern "C" fn [<clone_ $ty:snake>](
+                ptr: *const ::std::ffi::c_void,
+            ) -> *const ::std::ffi::c_void {
+                ::std::sync::Arc::increment_strong_count(ptr as *const $ty);
+                ptr
+            }
+
+            /// # Safety
+            ///
+            /// `ptr` must be null or a live handle previously returned to foreign code, it must not
+            /// be used after this call.
+            #[no_mangle]
+            pub unsafe extern "C" fn [<free_ $ty:snake>](ptr: *const ::std::ffi::c_void) {
+                if !ptr.is_null() {
+                    drop(::std::sync::Arc::from_raw(ptr as *const $ty));
+                }
+            }
+        }
+    };
+}
@@ -1,6 +1,9 @@
 use std::mem::ManuallyDrop;
 
-use crate::ffi::buffer::FFIBuffer;
+use crate::{
+    converter_traits::{Lower, LowerError},
+    ffi::{buffer::FFIBuffer, default::FFIDefault},
+};
 
 #[repr(C)]
 pub struct FFIErrStatus {
@@ -8,6 +11,44 @@ pub struct FFIErrStatus {
     pub error: ManuallyDrop<FFIBuffer>,
 }
 
+impl FFIErrStatus {
+    pub fn set_error<UT, E: LowerError<UT>>(&mut self, err: E) {
+        self.code = FFIStatusCode::Error.into();
+        self.error = ManuallyDrop::new(E::lower_error(err));
+    }
+
+    /// Reports an error that is not part of the function signature, e.g. an invalid argument,
+    /// with its message as the UTF-8 error buffer.
+    pub fn set_unexpected_error(&mut self, err: anyhow::Error) {
+        self.code = FFIStatusCode::UnexpectedError.into();
+        self.error = ManuallyDrop::new(FFIBuffer::from_vec(err.to_string().into_bytes()));
+    }
+}
+
+impl Default for FFIErrStatus {
+    fn default() -> Self {
+        Self {
+            code: FFIStatusCode::Success.into(),
+            error: ManuallyDrop::ffi_default(),
+        }
+    }
+}
+
+pub fn call_with_result<UT, T, E, F>(status: &mut FFIErrStatus, f: F) -> T::FFIType
+where
+    F: FnOnce() -> Result<T, E>,
+    T: Lower<UT>,
+    E: LowerError<UT>,
+{
+    match f() {
+        Ok(v) => T::lower(v),
+        Err(e) => {
+            status.set_error(e);
+            T::FFIType::ffi_default()
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum FFIStatusCode {
     Success,
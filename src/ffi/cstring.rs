@@ -0,0 +1,61 @@
+use std::{
+    ffi::{c_char, CStr, CString},
+    marker::PhantomData,
+    ptr,
+};
+
+use crate::ffi::call::FFIErrStatus;
+
+/// `const char*` argument passed in by foreign code, lifted as [crate::types::CStrArg].
+///
+/// The lifetime is the time the pointer stays valid, which for an argument is the call it was
+/// passed to.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+pub struct FFICStr<'a> {
+    ptr: *const c_char,
+    _marker: PhantomData<&'a CStr>,
+}
+
+impl FFICStr<'_> {
+    /// # Safety
+    ///
+    /// `ptr` must be null or point to a NUL-terminated string that stays valid and unmodified for
+    /// the lifetime of the returned value.
+    pub unsafe fn from_ptr(ptr: *const c_char) -> Self {
+        Self {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const c_char {
+        self.ptr
+    }
+}
+
+/// Lowers a string to an owned C string for foreign code to free with `ffihelper_cstring_free`.
+///
+/// A string with an interior NUL would be silently truncated on the foreign side, so it's
+/// reported through `status` and a null pointer is returned instead.
+pub fn lower_string(s: String, status: &mut FFIErrStatus) -> *mut c_char {
+    match CString::new(s) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            status.set_unexpected_error(e.into());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Frees a string returned to foreign code as a `CString`.
+///
+/// # Safety
+///
+/// `ptr` must be null or a pointer returned by lowering a `CString` that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ffihelper_cstring_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(CString::from_raw(ptr));
+    }
+}
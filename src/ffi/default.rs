@@ -1,6 +1,9 @@
 use std::mem::ManuallyDrop;
 
-use crate::ffi::buffer::FFIBuffer;
+use crate::ffi::{
+    buffer::FFIBuffer,
+    int128::{FFII128, FFIU128},
+};
 
 pub trait FFIDefault {
     fn ffi_default() -> Self;
@@ -22,7 +25,7 @@ macro_rules! impl_ffi_default_with_default {
 }
 
 impl_ffi_default_with_default! {
-    bool, i8, u8, i16, u16, i32, u32, i64, u64, f32, f64
+    bool, i8, u8, i16, u16, i32, u32, i64, u64, f32, f64, FFIU128, FFII128
 }
 
 impl FFIDefault for () {
@@ -35,6 +38,18 @@ impl FFIDefault for *const std::ffi::c_void {
     }
 }
 
+impl FFIDefault for *const std::ffi::c_char {
+    fn ffi_default() -> Self {
+        std::ptr::null()
+    }
+}
+
+impl FFIDefault for *mut std::ffi::c_char {
+    fn ffi_default() -> Self {
+        std::ptr::null_mut()
+    }
+}
+
 impl FFIDefault for FFIBuffer {
     fn ffi_default() -> Self {
         unsafe { Self::from_raw_parts(std::ptr::null_mut(), 0, 0) }
@@ -0,0 +1,43 @@
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FFIU128 {
+    pub low: u64,
+    pub high: u64,
+}
+
+impl From<u128> for FFIU128 {
+    fn from(v: u128) -> Self {
+        Self {
+            low: v as u64,
+            high: (v >> 64) as u64,
+        }
+    }
+}
+
+impl From<FFIU128> for u128 {
+    fn from(v: FFIU128) -> Self {
+        (u128::from(v.high) << 64) | u128::from(v.low)
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FFII128 {
+    pub low: u64,
+    pub high: i64,
+}
+
+impl From<i128> for FFII128 {
+    fn from(v: i128) -> Self {
+        Self {
+            low: v as u64,
+            high: (v >> 64) as i64,
+        }
+    }
+}
+
+impl From<FFII128> for i128 {
+    fn from(v: FFII128) -> Self {
+        (i128::from(v.high) << 64) | i128::from(v.low)
+    }
+}
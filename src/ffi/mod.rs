@@ -1,4 +1,6 @@
 pub mod buffer;
 pub mod call;
+pub mod cstring;
 pub mod default;
 pub mod foreignbytes;
+pub mod int128;
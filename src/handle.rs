@@ -0,0 +1,218 @@
+use std::{ffi::c_void, marker::PhantomData, ptr, sync::Arc, sync::RwLock};
+
+use anyhow::bail;
+use bytes::{Buf, BufMut};
+
+use crate::{
+    check_remaining,
+    converter_traits::{FFIObject, ObjectHandle},
+    ffi::default::FFIDefault,
+    metadata::{checksum_metadata, codes},
+    FFIResult,
+};
+
+/// Passes `Arc<T>` to foreign code as the raw pointer from [Arc::into_raw].
+///
+/// The pointer is trusted as-is, so it can only be lifted from a [RawObjectPtr] foreign code passed
+/// in directly. Raw handles can't be stored in buffers, use `handle_map` for objects that are.
+pub struct RawHandle;
+
+/// `Arc<T>` pointer handed to and received from foreign code by [RawHandle].
+#[repr(transparent)]
+#[derive(Debug)]
+pub struct RawObjectPtr<T>(*const c_void, PhantomData<*const T>);
+
+impl<T> RawObjectPtr<T> {
+    /// # Safety
+    ///
+    /// `ptr` must be null or a live handle to a `T` previously returned to foreign code, that stays
+    /// live for as long as the returned value is used.
+    pub unsafe fn from_raw(ptr: *const c_void) -> Self {
+        Self(ptr, PhantomData)
+    }
+
+    pub fn as_ptr(&self) -> *const c_void {
+        self.0
+    }
+}
+
+impl<T> FFIDefault for RawObjectPtr<T> {
+    fn ffi_default() -> Self {
+        unsafe { Self::from_raw(ptr::null()) }
+    }
+}
+
+// Lowering hands a strong reference over to foreign code, which releases it with the exported
+// `free_<type>` function. Lifting borrows the handle, foreign code keeps its own reference.
+unsafe impl<T: FFIObject> ObjectHandle<T> for RawHandle {
+    type FFIType = RawObjectPtr<T>;
+    const KIND: u8 = codes::OBJECT_HANDLE_RAW;
+
+    fn lower(obj: Arc<T>) -> Self::FFIType {
+        unsafe { RawObjectPtr::from_raw(Arc::into_raw(obj) as *const c_void) }
+    }
+
+    fn write(_obj: Arc<T>, _buf: &mut Vec<u8>) {
+        panic!(
+            "{} uses raw handles, which can't be written to buffers",
+            T::NAME
+        )
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Arc<T>> {
+        if v.as_ptr().is_null() {
+            bail!("unexpected null handle for {}", T::NAME);
+        }
+        unsafe {
+            Arc::increment_strong_count(v.as_ptr() as *const T);
+            Ok(Arc::from_raw(v.as_ptr() as *const T))
+        }
+    }
+
+    fn try_read(_buf: &mut &[u8]) -> FFIResult<Arc<T>> {
+        bail!(
+            "{} uses raw handles, which can only be passed as direct arguments",
+            T::NAME
+        )
+    }
+}
+
+/// Object whose `Arc<Self>` handles are stored in a [HandleMap], see [crate::ffi_object].
+pub trait MappedObject: FFIObject {
+    fn handle_map() -> &'static HandleMap<Arc<Self>>;
+}
+
+/// Passes `Arc<T>` to foreign code as a `u64` handle into the object's [HandleMap].
+pub struct MappedHandle;
+
+unsafe impl<T: MappedObject + ?Sized> ObjectHandle<T> for MappedHandle {
+    type FFIType = u64;
+    const KIND: u8 = codes::OBJECT_HANDLE_MAPPED;
+
+    fn lower(obj: Arc<T>) -> Self::FFIType {
+        T::handle_map().insert(obj)
+    }
+
+    fn write(obj: Arc<T>, buf: &mut Vec<u8>) {
+        buf.put_u64(<Self as ObjectHandle<T>>::lower(obj));
+    }
+
+    fn try_lift(v: Self::FFIType) -> FFIResult<Arc<T>> {
+        T::handle_map().get(v)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> FFIResult<Arc<T>> {
+        check_remaining(buf, 8)?;
+        <Self as ObjectHandle<T>>::try_lift(buf.get_u64())
+    }
+}
+
+const INDEX_BITS: u32 = 32;
+const GENERATION_BITS: u32 = 16;
+
+/// Slab of values addressed by `u64` handles.
+///
+/// A handle packs the map tag (bits 48..64), the slot generation (bits 32..48) and the slot index
+/// (bits 0..32). Removing a value bumps the slot generation, so stale handles and handles from
+/// another map are rejected instead of aliasing a different value.
+pub struct HandleMap<T> {
+    tag: u16,
+    slots: RwLock<Slots<T>>,
+}
+
+struct Slots<T> {
+    entries: Vec<Slot<T>>,
+    free: Vec<u32>,
+}
+
+struct Slot<T> {
+    generation: u16,
+    value: Option<T>,
+}
+
+impl<T> HandleMap<T> {
+    pub const fn new(tag: u16) -> Self {
+        Self {
+            tag,
+            slots: RwLock::new(Slots {
+                entries: Vec::new(),
+                free: Vec::new(),
+            }),
+        }
+    }
+
+    /// Creates a map tagged with the checksum of `name`, which should be the fully qualified type
+    /// name so that same-named types in different modules get different tags.
+    pub const fn for_type(name: &str) -> Self {
+        Self::new(checksum_metadata(name.as_bytes()))
+    }
+
+    pub fn insert(&self, value: T) -> u64 {
+        let mut slots = self.slots.write().unwrap();
+        let index = match slots.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = u32::try_from(slots.entries.len()).expect("handle map is full");
+                slots.entries.push(Slot {
+                    generation: 1,
+                    value: None,
+                });
+                index
+            }
+        };
+        let slot = &mut slots.entries[index as usize];
+        slot.value = Some(value);
+        self.handle(index, slot.generation)
+    }
+
+    pub fn get(&self, handle: u64) -> FFIResult<T>
+    where
+        T: Clone,
+    {
+        let slots = self.slots.read().unwrap();
+        let index = self.check(&slots, handle)?;
+        Ok(slots.entries[index].value.clone().unwrap())
+    }
+
+    pub fn remove(&self, handle: u64) -> FFIResult<T> {
+        let mut slots = self.slots.write().unwrap();
+        let index = self.check(&slots, handle)?;
+        let slot = &mut slots.entries[index];
+        // generation 0 is never handed out, so a null handle is always invalid
+        slot.generation = match slot.generation.wrapping_add(1) {
+            0 => 1,
+            n => n,
+        };
+        let value = slot.value.take().unwrap();
+        slots.free.push(index as u32);
+        Ok(value)
+    }
+
+    /// Inserts another handle to the value behind `handle`.
+    pub fn clone_handle(&self, handle: u64) -> FFIResult<u64>
+    where
+        T: Clone,
+    {
+        let value = self.get(handle)?;
+        Ok(self.insert(value))
+    }
+
+    fn handle(&self, index: u32, generation: u16) -> u64 {
+        (u64::from(self.tag) << (INDEX_BITS + GENERATION_BITS))
+            | (u64::from(generation) << INDEX_BITS)
+            | u64::from(index)
+    }
+
+    fn check(&self, slots: &Slots<T>, handle: u64) -> FFIResult<usize> {
+        let tag = (handle >> (INDEX_BITS + GENERATION_BITS)) as u16;
+        let generation = (handle >> INDEX_BITS) as u16;
+        let index = handle as u32 as usize;
+        if tag != self.tag {
+            bail!("handle {handle:#x} belongs to a different type");
+        }
+        match slots.entries.get(index) {
+            Some(slot) if slot.generation == generation && slot.value.is_some() => Ok(index),
+            _ => bail!("stale or invalid handle {handle:#x}"),
+        }
+    }
+}
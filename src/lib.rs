@@ -2,11 +2,21 @@ use anyhow::bail;
 use bytes::Buf;
 
 pub mod ffi;
+pub mod handle;
 pub mod metadata;
+pub mod types;
 
 mod converter_impls;
 mod converter_traits;
-pub use converter_traits::{FFIConverter, Lift, Lower, TypeId};
+pub use converter_traits::{
+    FFIConverter, FFIObject, Lift, Lower, LowerError, ObjectHandle, TypeId,
+};
+pub use ffihelper_macros::{ffi_trait, FFIEnum, FFIError, FFIRecord};
+
+#[doc(hidden)]
+pub use anyhow;
+#[doc(hidden)]
+pub use paste;
 
 pub type FFIResult<T> = anyhow::Result<T>;
 
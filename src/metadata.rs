@@ -16,6 +16,57 @@ pub mod codes {
     pub const TYPE_OPTION: u8 = 12;
     pub const TYPE_VEC: u8 = 13;
     pub const TYPE_HASH_MAP: u8 = 14;
+    pub const TYPE_U128: u8 = 15;
+    pub const TYPE_I128: u8 = 16;
+    pub const TYPE_USIZE: u8 = 17;
+    pub const TYPE_ISIZE: u8 = 18;
+    pub const TYPE_CHAR: u8 = 19;
+    pub const TYPE_TUPLE: u8 = 20;
+    pub const TYPE_ARRAY: u8 = 21;
+    pub const TYPE_RESULT: u8 = 22;
+    pub const TYPE_BTREE_MAP: u8 = 23;
+    pub const TYPE_BTREE_SET: u8 = 24;
+    pub const TYPE_HASH_SET: u8 = 25;
+    pub const TYPE_VEC_DEQUE: u8 = 26;
+    pub const TYPE_BYTES: u8 = 27;
+    pub const TYPE_DURATION: u8 = 28;
+    pub const TYPE_SYSTEM_TIME: u8 = 29;
+    pub const TYPE_IPV4_ADDR: u8 = 30;
+    pub const TYPE_IPV6_ADDR: u8 = 31;
+    pub const TYPE_IP_ADDR: u8 = 32;
+    pub const TYPE_SOCKET_ADDR_V4: u8 = 33;
+    pub const TYPE_SOCKET_ADDR_V6: u8 = 34;
+    pub const TYPE_SOCKET_ADDR: u8 = 35;
+    pub const TYPE_OS_STRING: u8 = 36;
+    pub const TYPE_PATH_BUF: u8 = 37;
+    pub const TYPE_NON_ZERO: u8 = 38;
+    pub const TYPE_WRAPPING: u8 = 39;
+    pub const TYPE_UNIT: u8 = 40;
+    pub const TYPE_CUSTOM: u8 = 41;
+    pub const TYPE_RECORD: u8 = 42;
+    pub const TYPE_ENUM: u8 = 43;
+    pub const TYPE_ERROR: u8 = 44;
+    pub const TYPE_FLAGS: u8 = 45;
+    pub const TYPE_REF: u8 = 46;
+    pub const TYPE_C_STRING: u8 = 47;
+    pub const TYPE_C_STR: u8 = 48;
+    pub const TYPE_UTF16_STRING: u8 = 49;
+    pub const TYPE_RANGE: u8 = 50;
+    pub const TYPE_RANGE_INCLUSIVE: u8 = 51;
+    pub const TYPE_BOUND: u8 = 52;
+    pub const TYPE_OBJECT: u8 = 53;
+    pub const TYPE_INTERFACE: u8 = 54;
+    pub const TYPE_BOUND_RANGE: u8 = 55;
+
+    pub const OS_STRING_BYTES: u8 = 0;
+    pub const OS_STRING_UTF16: u8 = 1;
+
+    pub const UNKNOWN_BITS_REJECT: u8 = 0;
+    pub const UNKNOWN_BITS_TRUNCATE: u8 = 1;
+    pub const UNKNOWN_BITS_RETAIN: u8 = 2;
+
+    pub const OBJECT_HANDLE_RAW: u8 = 0;
+    pub const OBJECT_HANDLE_MAPPED: u8 = 1;
 }
 
 const BUF_SIZE: usize = 16384;
@@ -67,6 +118,19 @@ impl MetadataBuffer {
         self
     }
 
+    pub const fn concat_u64(mut self, value: u64) -> Self {
+        assert!(self.size + 8 <= BUF_SIZE);
+        // store the value as little-endian
+        let bytes = value.to_le_bytes();
+        let mut i = 0;
+        while i < 8 {
+            self.bytes[self.size] = bytes[i];
+            self.size += 1;
+            i += 1;
+        }
+        self
+    }
+
     pub const fn concat_bool(self, value: bool) -> Self {
         self.concat_value(value as u8)
     }
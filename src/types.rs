@@ -0,0 +1,123 @@
+use std::{
+    ffi::{c_char, CStr},
+    ops::{Bound, Deref, DerefMut, RangeBounds},
+};
+
+use crate::ffi::cstring::FFICStr;
+
+/// Byte payload that is transferred as a single block instead of element by element.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(v: Vec<u8>) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(v: Bytes) -> Self {
+        v.0
+    }
+}
+
+impl Deref for Bytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Bytes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// String that is transferred as UTF-16 code units instead of UTF-8 bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Utf16String(pub String);
+
+impl Utf16String {
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for Utf16String {
+    fn from(v: String) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Utf16String> for String {
+    fn from(v: Utf16String) -> Self {
+        v.0
+    }
+}
+
+impl Deref for Utf16String {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Utf16String {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Range with arbitrary bounds on either side, e.g. `(Bound::Excluded(a), Bound::Unbounded)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoundRange<T> {
+    pub start: Bound<T>,
+    pub end: Bound<T>,
+}
+
+impl<T> From<(Bound<T>, Bound<T>)> for BoundRange<T> {
+    fn from((start, end): (Bound<T>, Bound<T>)) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T> From<BoundRange<T>> for (Bound<T>, Bound<T>) {
+    fn from(v: BoundRange<T>) -> Self {
+        (v.start, v.end)
+    }
+}
+
+impl<T> RangeBounds<T> for BoundRange<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end.as_ref()
+    }
+}
+
+/// Borrowed NUL-terminated string argument, only valid for the duration of the call.
+///
+/// Lifted from the [FFICStr] foreign code passed in, and borrows for as long as that pointer does.
+#[derive(Debug)]
+pub struct CStrArg<'a>(pub(crate) FFICStr<'a>);
+
+impl CStrArg<'_> {
+    pub fn as_c_str(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.0.as_ptr()) }
+    }
+
+    pub fn as_ptr(&self) -> *const c_char {
+        self.0.as_ptr()
+    }
+}
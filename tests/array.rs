@@ -0,0 +1,44 @@
+mod common;
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn arrays_round_trip() {
+    let key = [0xabu8; 32];
+    assert_eq!(round_trip(key), key);
+    let matrix = [[1.0f64, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    assert_eq!(round_trip(matrix), matrix);
+    assert_eq!(round_trip::<[u8; 0]>([]), []);
+
+    let buf = <[u16; 2] as Lower<UT>>::lower([1, 2]);
+    assert_eq!(<[u16; 2] as Lift<UT>>::try_lift(buf).unwrap(), [1, 2]);
+}
+
+#[test]
+fn arrays_are_length_prefixed() {
+    assert_eq!(write([1u8, 2, 3]), [0, 0, 0, 3, 1, 2, 3]);
+}
+
+#[test]
+fn length_mismatch_is_rejected() {
+    let err = read::<[u8; 3]>(&write(vec![1u8, 2])).unwrap_err();
+    assert!(err.to_string().contains("expected 3, got 2"));
+    assert!(read::<[u8; 3]>(&write(vec![1u8, 2, 3, 4])).is_err());
+    assert!(read::<[u8; 3]>(&write([1u8, 2, 3])[..6]).is_err());
+}
+
+#[test]
+fn array_metadata_embeds_the_length() {
+    let mut expected = vec![codes::TYPE_ARRAY];
+    expected.extend(32u32.to_le_bytes());
+    expected.push(codes::TYPE_U8);
+    assert_eq!(meta::<[u8; 32]>(), expected);
+
+    let mut expected = vec![codes::TYPE_ARRAY];
+    expected.extend(3u32.to_le_bytes());
+    expected.push(codes::TYPE_ARRAY);
+    expected.extend(3u32.to_le_bytes());
+    expected.push(codes::TYPE_F64);
+    assert_eq!(meta::<[[f64; 3]; 3]>(), expected);
+}
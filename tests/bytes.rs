@@ -0,0 +1,41 @@
+mod common;
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, types::Bytes, Lift, Lower};
+
+#[test]
+fn bytes_round_trip() {
+    let payload = Bytes((0..=255).collect());
+    assert_eq!(round_trip(payload.clone()), payload);
+    let shared = bytes::Bytes::from_static(b"image");
+    assert_eq!(round_trip(shared.clone()), shared);
+    assert_eq!(round_trip(Bytes::default()), Bytes::default());
+}
+
+#[test]
+fn bytes_are_written_as_one_block() {
+    assert_eq!(write(Bytes(vec![1, 2, 3])), [0, 0, 0, 3, 1, 2, 3]);
+    assert_eq!(
+        write(bytes::Bytes::from_static(&[1, 2, 3])),
+        [0, 0, 0, 3, 1, 2, 3]
+    );
+    // same layout as the element-by-element Vec<u8> encoding
+    assert_eq!(write(Bytes(vec![4, 5])), write(vec![4u8, 5]));
+    assert!(read::<Bytes>(&[0, 0, 0, 3, 1, 2]).is_err());
+}
+
+#[test]
+fn bytes_lower_directly_to_a_buffer() {
+    let buf = <Bytes as Lower<UT>>::lower(Bytes(vec![7, 8, 9]));
+    assert_eq!(buf.len(), 3);
+    assert_eq!(
+        <bytes::Bytes as Lift<UT>>::try_lift(buf).unwrap(),
+        bytes::Bytes::from_static(&[7, 8, 9])
+    );
+}
+
+#[test]
+fn bytes_metadata() {
+    assert_eq!(meta::<Bytes>(), [codes::TYPE_BYTES]);
+    assert_eq!(meta::<bytes::Bytes>(), [codes::TYPE_BYTES]);
+}
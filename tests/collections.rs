@@ -0,0 +1,51 @@
+mod common;
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+use common::{meta, round_trip, write};
+use ffihelper::metadata::codes;
+
+#[test]
+fn ordered_collections_round_trip() {
+    let map = BTreeMap::from([(3u8, "c".to_owned()), (1, "a".to_owned())]);
+    assert_eq!(round_trip(map.clone()), map);
+    let set = BTreeSet::from([5u16, 1, 3]);
+    assert_eq!(round_trip(set.clone()), set);
+    let deque = VecDeque::from([3u8, 1, 2]);
+    assert_eq!(round_trip(deque.clone()), deque);
+    let hash_set = HashSet::from(["x".to_owned(), "y".to_owned()]);
+    assert_eq!(round_trip(hash_set.clone()), hash_set);
+    assert_eq!(round_trip(BTreeMap::<u8, u8>::new()), BTreeMap::new());
+}
+
+#[test]
+fn ordered_collections_are_written_in_order() {
+    let map = BTreeMap::from([(3u8, 30u8), (1, 10), (2, 20)]);
+    assert_eq!(write(map), [0, 0, 0, 3, 1, 10, 2, 20, 3, 30]);
+    let set = BTreeSet::from([9u8, 4, 7]);
+    assert_eq!(write(set), [0, 0, 0, 3, 4, 7, 9]);
+
+    let mut deque = VecDeque::from([2u8, 3]);
+    deque.push_front(1);
+    assert_eq!(write(deque), [0, 0, 0, 3, 1, 2, 3]);
+}
+
+#[test]
+fn collection_metadata() {
+    assert_eq!(
+        meta::<BTreeMap<u8, String>>(),
+        [codes::TYPE_BTREE_MAP, codes::TYPE_U8, codes::TYPE_STRING]
+    );
+    assert_eq!(
+        meta::<BTreeSet<u16>>(),
+        [codes::TYPE_BTREE_SET, codes::TYPE_U16]
+    );
+    assert_eq!(
+        meta::<HashSet<String>>(),
+        [codes::TYPE_HASH_SET, codes::TYPE_STRING]
+    );
+    assert_eq!(
+        meta::<VecDeque<u8>>(),
+        [codes::TYPE_VEC_DEQUE, codes::TYPE_U8]
+    );
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+use ffihelper::{FFIResult, Lift, Lower, TypeId};
+
+pub struct UT;
+
+pub fn write<T: Lower<UT>>(obj: T) -> Vec<u8> {
+    let mut buf = Vec::new();
+    T::write(obj, &mut buf);
+    buf
+}
+
+/// Reads a `T` and checks that it consumed the whole buffer.
+pub fn read<T: Lift<UT>>(buf: &[u8]) -> FFIResult<T> {
+    let mut slice = buf;
+    let res = T::try_read(&mut slice)?;
+    assert!(slice.is_empty(), "{} trailing bytes", slice.len());
+    Ok(res)
+}
+
+pub fn round_trip<T: Lower<UT> + Lift<UT>>(obj: T) -> T {
+    read(&write(obj)).unwrap()
+}
+
+pub fn meta<T: TypeId<UT> + ?Sized>() -> Vec<u8> {
+    T::TYPE_ID_META.as_ref().to_vec()
+}
+
+/// The metadata bytes `concat_str` appends for `s`.
+pub fn meta_str(s: &str) -> Vec<u8> {
+    let mut res = vec![s.len() as u8];
+    res.extend_from_slice(s.as_bytes());
+    res
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    mem::ManuallyDrop,
+    ptr,
+};
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{
+    ffi::{
+        call::{FFIErrStatus, FFIStatusCode},
+        cstring::{ffihelper_cstring_free, lower_string, FFICStr},
+    },
+    metadata::codes,
+    types::CStrArg,
+    Lift, Lower,
+};
+
+fn lift_arg(ptr: *const c_char) -> ffihelper::FFIResult<String> {
+    let arg = <CStrArg<'_> as Lift<UT>>::try_lift(unsafe { FFICStr::from_ptr(ptr) })?;
+    Ok(arg.as_c_str().to_str()?.to_owned())
+}
+
+#[test]
+fn lowered_strings_are_freed_by_foreign_code() {
+    let ptr = <CString as Lower<UT>>::lower(CString::new("hello").unwrap());
+    assert_eq!(unsafe { CStr::from_ptr(ptr) }, c"hello");
+    unsafe { ffihelper_cstring_free(ptr) };
+    unsafe { ffihelper_cstring_free(ptr::null_mut()) };
+}
+
+#[test]
+fn interior_nul_is_reported() {
+    let mut status = FFIErrStatus::default();
+    let ptr = lower_string("a\0b".to_owned(), &mut status);
+    assert!(ptr.is_null());
+    assert_eq!(
+        FFIStatusCode::try_from(status.code),
+        Ok(FFIStatusCode::UnexpectedError)
+    );
+    let msg = unsafe { ManuallyDrop::take(&mut status.error) }.destroy_into_vec();
+    assert!(String::from_utf8(msg).unwrap().contains("nul byte"));
+
+    let ptr = lower_string("ab".to_owned(), &mut status);
+    assert_eq!(unsafe { CStr::from_ptr(ptr) }, c"ab");
+    unsafe { ffihelper_cstring_free(ptr) };
+}
+
+#[test]
+fn c_strings_are_length_prefixed_in_buffers() {
+    let s = CString::new("hi").unwrap();
+    assert_eq!(write(s.clone()), [0, 0, 0, 2, b'h', b'i']);
+    assert_eq!(round_trip(s.clone()), s);
+    assert_eq!(
+        round_trip(vec![s.clone(), CString::default()]),
+        [s, CString::default()]
+    );
+    assert!(read::<CString>(&[0, 0, 0, 2, b'h', 0]).is_err());
+
+    let buf = <CString as Lower<UT>>::lower_into_buffer(c"hi".to_owned());
+    assert_eq!(<CString as Lift<UT>>::try_lift(buf).unwrap(), c"hi");
+}
+
+#[test]
+fn c_str_args_borrow_the_pointer() {
+    let s = CString::new("borrowed").unwrap();
+    assert_eq!(lift_arg(s.as_ptr()).unwrap(), "borrowed");
+
+    let arg =
+        <CStrArg<'_> as Lift<UT>>::try_lift(unsafe { FFICStr::from_ptr(s.as_ptr()) }).unwrap();
+    assert_eq!(arg.as_ptr(), s.as_ptr());
+
+    let err = lift_arg(ptr::null()).unwrap_err();
+    assert!(err.to_string().contains("unexpected null pointer"));
+    assert!(read::<CStrArg<'_>>(&[0, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn c_str_args_cannot_outlive_their_pointer() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/cstr_*.rs");
+}
+
+#[test]
+fn c_string_metadata() {
+    assert_eq!(meta::<CString>(), [codes::TYPE_C_STRING]);
+    assert_eq!(meta::<CStrArg<'_>>(), [codes::TYPE_C_STR]);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use std::num::TryFromIntError;
+
+use common::{meta, meta_str, read, round_trip, write, UT};
+use ffihelper::{anyhow::bail, custom_type, metadata::codes, Lift, Lower};
+
+/// Converted with `TryFrom` and `From` impls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Port(u16);
+
+impl TryFrom<u32> for Port {
+    type Error = TryFromIntError;
+
+    fn try_from(v: u32) -> Result<Self, Self::Error> {
+        u16::try_from(v).map(Port)
+    }
+}
+
+impl From<Port> for u32 {
+    fn from(v: Port) -> Self {
+        u32::from(v.0)
+    }
+}
+
+custom_type!(Port, u32);
+
+/// Validated with a closure, lowered with `From`.
+#[derive(Debug, Clone, PartialEq)]
+struct Email(String);
+
+impl From<Email> for String {
+    fn from(v: Email) -> Self {
+        v.0
+    }
+}
+
+custom_type!(
+    Email,
+    String,
+    try_into = |s: String| {
+        if !s.contains('@') {
+            bail!("invalid email {s:?}");
+        }
+        Ok(Email(s))
+    }
+);
+
+/// Lifted with `TryFrom`, lowered with a closure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Even(u8);
+
+impl TryFrom<u8> for Even {
+    type Error = std::fmt::Error;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        if v.is_multiple_of(2) {
+            Ok(Even(v))
+        } else {
+            Err(std::fmt::Error)
+        }
+    }
+}
+
+custom_type!(Even, u8, from = |v: Even| v.0);
+
+/// Both directions given as closures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UserId(u64);
+
+custom_type!(
+    UserId,
+    u64,
+    try_into = |v: u64| Ok(UserId(v)),
+    from = |v: UserId| v.0,
+);
+
+#[test]
+fn custom_types_use_the_builtin_encoding() {
+    assert_eq!(round_trip(Port(8080)), Port(8080));
+    assert_eq!(write(Port(1)), write(1u32));
+    assert_eq!(<Port as Lower<UT>>::lower(Port(7)), 7);
+
+    let email = Email("a@b.c".to_owned());
+    assert_eq!(round_trip(email.clone()), email);
+    assert_eq!(write(email), write("a@b.c".to_owned()));
+
+    assert_eq!(round_trip(Even(4)), Even(4));
+    assert_eq!(round_trip(UserId(u64::MAX)), UserId(u64::MAX));
+    assert_eq!(<UserId as Lift<UT>>::try_lift(3).unwrap(), UserId(3));
+}
+
+#[test]
+fn conversion_errors_fail_lifting() {
+    assert!(<Port as Lift<UT>>::try_lift(70_000).is_err());
+    assert!(read::<Port>(&70_000u32.to_be_bytes()).is_err());
+
+    let err = read::<Email>(&write("nope".to_owned())).unwrap_err();
+    assert!(err.to_string().contains("invalid email"));
+
+    assert!(<Even as Lift<UT>>::try_lift(3).is_err());
+    assert!(read::<Even>(&[5]).is_err());
+}
+
+#[test]
+fn custom_type_metadata_records_name_and_builtin() {
+    let mut expected = vec![codes::TYPE_CUSTOM];
+    expected.extend(meta_str("Port"));
+    expected.push(codes::TYPE_U32);
+    assert_eq!(meta::<Port>(), expected);
+
+    let mut expected = vec![codes::TYPE_CUSTOM];
+    expected.extend(meta_str("Email"));
+    expected.push(codes::TYPE_STRING);
+    assert_eq!(meta::<Email>(), expected);
+
+    let mut expected = vec![codes::TYPE_OPTION, codes::TYPE_CUSTOM];
+    expected.extend(meta_str("UserId"));
+    expected.push(codes::TYPE_U64);
+    assert_eq!(meta::<Option<UserId>>(), expected);
+}
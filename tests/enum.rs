@@ -0,0 +1,107 @@
+mod common;
+
+use common::{meta, meta_str, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, FFIEnum, FFIRecord, Lift, Lower};
+
+#[derive(Debug, Clone, Copy, PartialEq, FFIEnum)]
+enum Color {
+    Red = 1,
+    Green = 2,
+    Blue = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FFIEnum)]
+#[repr(i64)]
+enum Wide {
+    Low = i32::MIN as i64,
+    High = i32::MAX as i64,
+}
+
+#[derive(Debug, Clone, PartialEq, FFIRecord)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, FFIEnum)]
+enum Shape {
+    Circle { radius: f64 },
+    Polygon(Vec<Point>),
+    Empty,
+}
+
+#[test]
+fn flat_enums_use_discriminants() {
+    assert_eq!(<Color as Lower<UT>>::lower(Color::Blue), 4);
+    assert_eq!(<Color as Lift<UT>>::try_lift(2).unwrap(), Color::Green);
+    assert_eq!(write(Color::Red), [0, 0, 0, 1]);
+    assert_eq!(round_trip(Color::Blue), Color::Blue);
+
+    let err = <Color as Lift<UT>>::try_lift(3).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown discriminant 3 for enum Color"));
+    assert!(read::<Color>(&[0, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn wide_reprs_within_i32_round_trip() {
+    assert_eq!(<Wide as Lower<UT>>::lower(Wide::Low), i32::MIN);
+    assert_eq!(round_trip(Wide::High), Wide::High);
+}
+
+#[test]
+fn data_enums_use_variant_index_tags() {
+    for shape in [
+        Shape::Circle { radius: 1.5 },
+        Shape::Polygon(vec![Point { x: 0, y: 1 }]),
+        Shape::Empty,
+    ] {
+        assert_eq!(round_trip(shape.clone()), shape);
+    }
+    assert_eq!(write(Shape::Empty), [0, 0, 0, 2]);
+
+    let err = read::<Shape>(&[0, 0, 0, 3]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unknown variant tag 3 for enum Shape"));
+}
+
+#[test]
+fn flat_enum_metadata() {
+    let mut expected = vec![codes::TYPE_ENUM];
+    expected.extend(meta_str("Color"));
+    // flat, three variants
+    expected.extend([1, 3]);
+    for (name, discriminant) in [("Red", 1u32), ("Green", 2), ("Blue", 4)] {
+        expected.extend(meta_str(name));
+        expected.extend(discriminant.to_le_bytes());
+        expected.push(0);
+    }
+    assert_eq!(meta::<Color>(), expected);
+}
+
+#[test]
+fn data_enum_metadata() {
+    let mut expected = vec![codes::TYPE_ENUM];
+    expected.extend(meta_str("Shape"));
+    expected.extend([0, 3]);
+    expected.extend(meta_str("Circle"));
+    expected.push(1);
+    expected.extend(meta_str("radius"));
+    expected.push(codes::TYPE_F64);
+    expected.extend(meta_str("Polygon"));
+    expected.push(1);
+    expected.extend(meta_str("0"));
+    expected.push(codes::TYPE_VEC);
+    expected.extend(meta::<Point>());
+    expected.extend(meta_str("Empty"));
+    expected.push(0);
+    assert_eq!(meta::<Shape>(), expected);
+}
+
+#[test]
+fn invalid_enums_fail_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/enum_*.rs");
+}
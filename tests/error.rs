@@ -0,0 +1,84 @@
+mod common;
+
+use std::mem::ManuallyDrop;
+
+use common::{meta, meta_str, UT};
+use ffihelper::{
+    ffi::call::{call_with_result, FFIErrStatus, FFIStatusCode},
+    metadata::codes,
+    FFIError, Lift, LowerError,
+};
+
+#[derive(Debug, PartialEq, FFIError)]
+enum StoreError {
+    NotFound { key: String },
+    Full,
+}
+
+#[derive(Debug, PartialEq, FFIError)]
+enum Code {
+    Busy = 1,
+    Closed = 2,
+}
+
+fn lookup(key: &str) -> Result<u32, StoreError> {
+    match key {
+        "a" => Ok(1),
+        "full" => Err(StoreError::Full),
+        _ => Err(StoreError::NotFound {
+            key: key.to_owned(),
+        }),
+    }
+}
+
+#[test]
+fn errors_lower_into_their_wire_format() {
+    let buf = <StoreError as LowerError<UT>>::lower_error(StoreError::Full);
+    assert_eq!(
+        <StoreError as Lift<UT>>::try_lift(buf).unwrap(),
+        StoreError::Full
+    );
+    let buf = <Code as LowerError<UT>>::lower_error(Code::Closed);
+    assert_eq!(buf.destroy_into_vec(), [0, 0, 0, 2]);
+}
+
+#[test]
+fn call_with_result_reports_errors_in_the_status() {
+    let mut status = FFIErrStatus::default();
+    let v = call_with_result::<UT, _, _, _>(&mut status, || lookup("a"));
+    assert_eq!(v, 1);
+    assert_eq!(
+        FFIStatusCode::try_from(status.code),
+        Ok(FFIStatusCode::Success)
+    );
+
+    let v = call_with_result::<UT, _, _, _>(&mut status, || lookup("b"));
+    assert_eq!(v, 0);
+    assert_eq!(
+        FFIStatusCode::try_from(status.code),
+        Ok(FFIStatusCode::Error)
+    );
+    let error = unsafe { ManuallyDrop::take(&mut status.error) };
+    assert_eq!(
+        <StoreError as Lift<UT>>::try_lift(error).unwrap(),
+        StoreError::NotFound {
+            key: "b".to_owned()
+        }
+    );
+}
+
+#[test]
+fn error_metadata_flags_the_enum() {
+    let mut expected = vec![codes::TYPE_ERROR, codes::TYPE_ENUM];
+    expected.extend(meta_str("StoreError"));
+    expected.extend([0, 2]);
+    expected.extend(meta_str("NotFound"));
+    expected.push(1);
+    expected.extend(meta_str("key"));
+    expected.push(codes::TYPE_STRING);
+    expected.extend(meta_str("Full"));
+    expected.push(0);
+    assert_eq!(meta::<StoreError>(), expected);
+
+    assert_eq!(&meta::<Code>()[..2], [codes::TYPE_ERROR, codes::TYPE_ENUM]);
+}
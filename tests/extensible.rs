@@ -0,0 +1,119 @@
+use ffihelper::{FFIRecord, Lift, Lower};
+
+struct UT;
+
+mod v1 {
+    use super::*;
+
+    #[derive(Debug, PartialEq, FFIRecord)]
+    #[ffi(extensible)]
+    pub struct Config {
+        pub name: String,
+    }
+}
+
+mod v2 {
+    use super::*;
+
+    #[derive(Debug, PartialEq, FFIRecord)]
+    #[ffi(extensible)]
+    pub struct Config {
+        pub name: String,
+        #[ffi(default)]
+        pub retries: u32,
+        #[ffi(default)]
+        pub marker: (),
+    }
+}
+
+#[derive(Debug, PartialEq, FFIRecord)]
+#[ffi(extensible)]
+struct Required {
+    name: String,
+    retries: u32,
+}
+
+#[derive(Debug, PartialEq, FFIRecord)]
+#[ffi(extensible)]
+struct Empty {
+    unit: (),
+}
+
+fn write<T: Lower<UT>>(obj: T) -> Vec<u8> {
+    let mut buf = Vec::new();
+    T::write(obj, &mut buf);
+    buf
+}
+
+/// Reads a `T` followed by a trailing `u8`, to check the whole record body was consumed.
+fn read<T: Lift<UT>>(buf: &[u8]) -> ffihelper::FFIResult<(T, u8)> {
+    <(T, u8) as Lift<UT>>::try_read(&mut &buf[..])
+}
+
+#[test]
+fn new_reader_old_writer() {
+    let mut buf = write(v1::Config {
+        name: "a".to_owned(),
+    });
+    buf.push(7);
+    let (config, trailer) = read::<v2::Config>(&buf).unwrap();
+    assert_eq!(
+        config,
+        v2::Config {
+            name: "a".to_owned(),
+            retries: 0,
+            marker: (),
+        }
+    );
+    assert_eq!(trailer, 7);
+}
+
+#[test]
+fn old_reader_new_writer() {
+    let mut buf = write(v2::Config {
+        name: "a".to_owned(),
+        retries: 3,
+        marker: (),
+    });
+    buf.push(7);
+    let (config, trailer) = read::<v1::Config>(&buf).unwrap();
+    assert_eq!(
+        config,
+        v1::Config {
+            name: "a".to_owned(),
+        }
+    );
+    assert_eq!(trailer, 7);
+}
+
+#[test]
+fn zero_sized_fields_round_trip() {
+    let mut buf = write(v2::Config {
+        name: String::new(),
+        retries: 0,
+        marker: (),
+    });
+    buf.push(7);
+    let (config, _) = read::<v2::Config>(&buf).unwrap();
+    assert_eq!(config.name, "");
+
+    let mut buf = write(Empty { unit: () });
+    buf.push(7);
+    assert_eq!(read::<Empty>(&buf).unwrap(), (Empty { unit: () }, 7));
+}
+
+#[test]
+fn missing_required_field_is_rejected() {
+    let mut buf = write(v1::Config {
+        name: "a".to_owned(),
+    });
+    buf.push(7);
+    let err = read::<Required>(&buf).unwrap_err();
+    assert!(err.to_string().contains("missing field retries"));
+}
+
+#[test]
+fn extensible_attrs_are_rejected_on_enums() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/extensible_*.rs");
+}
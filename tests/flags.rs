@@ -0,0 +1,100 @@
+mod common;
+
+use common::{meta, meta_str, read, round_trip, write, UT};
+use ffihelper::{ffi_flags, metadata::codes, Lift, Lower};
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Strict: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Lossy: u32 {
+        const A = 1;
+        const B = 1 << 1;
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Open: u8 {
+        const A = 1;
+        const B = 1 << 7;
+    }
+}
+
+ffi_flags!(Strict, u32, unknown_bits = reject, [A, B]);
+ffi_flags!(Lossy, u32, unknown_bits = truncate, [A, B]);
+ffi_flags!(Open, u8, unknown_bits = retain, [A, B]);
+
+#[test]
+fn flags_round_trip_as_their_bits() {
+    assert_eq!(<Strict as Lower<UT>>::lower(Strict::A | Strict::B), 3);
+    assert_eq!(write(Strict::B), [0, 0, 0, 2]);
+    assert_eq!(round_trip(Strict::A | Strict::B), Strict::A | Strict::B);
+    assert_eq!(round_trip(Lossy::empty()), Lossy::empty());
+    assert_eq!(round_trip(Open::B), Open::B);
+}
+
+#[test]
+fn reject_fails_on_unknown_bits() {
+    let err = <Strict as Lift<UT>>::try_lift(0b101).unwrap_err();
+    assert!(err.to_string().contains("unknown bits set for Strict"));
+    assert!(read::<Strict>(&[0, 0, 0, 4]).is_err());
+}
+
+#[test]
+fn truncate_drops_unknown_bits() {
+    assert_eq!(<Lossy as Lift<UT>>::try_lift(0b101).unwrap(), Lossy::A);
+    assert_eq!(read::<Lossy>(&[0, 0, 0, 4]).unwrap(), Lossy::empty());
+}
+
+#[test]
+fn retain_keeps_unknown_bits() {
+    let open = <Open as Lift<UT>>::try_lift(0b11).unwrap();
+    assert_eq!(open.bits(), 0b11);
+    assert!(open.contains(Open::A));
+    assert_eq!(write(open), [0b11]);
+}
+
+fn flags_meta(name: &str, repr: u8, policy: u8, flags: &[(&str, u64)]) -> Vec<u8> {
+    let mut expected = vec![codes::TYPE_FLAGS];
+    expected.extend(meta_str(name));
+    expected.extend([repr, policy, flags.len() as u8]);
+    for (flag, bits) in flags {
+        expected.extend(meta_str(flag));
+        expected.extend(bits.to_le_bytes());
+    }
+    expected
+}
+
+#[test]
+fn flags_metadata_lists_policy_and_bits() {
+    assert_eq!(
+        meta::<Strict>(),
+        flags_meta(
+            "Strict",
+            codes::TYPE_U32,
+            codes::UNKNOWN_BITS_REJECT,
+            &[("A", 1), ("B", 2)]
+        )
+    );
+    assert_eq!(
+        meta::<Lossy>(),
+        flags_meta(
+            "Lossy",
+            codes::TYPE_U32,
+            codes::UNKNOWN_BITS_TRUNCATE,
+            &[("A", 1), ("B", 2)]
+        )
+    );
+    assert_eq!(
+        meta::<Open>(),
+        flags_meta(
+            "Open",
+            codes::TYPE_U8,
+            codes::UNKNOWN_BITS_RETAIN,
+            &[("A", 1), ("B", 128)]
+        )
+    );
+}
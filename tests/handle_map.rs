@@ -0,0 +1,119 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{meta, meta_str, read, write, UT};
+use ffihelper::{
+    ffi::call::{FFIErrStatus, FFIStatusCode},
+    ffi_object,
+    handle::HandleMap,
+    metadata::codes,
+    Lift, Lower,
+};
+
+#[derive(Debug)]
+pub struct Counter(u32);
+
+ffi_object!(Counter, handle_map);
+
+fn status_code(status: &FFIErrStatus) -> FFIStatusCode {
+    FFIStatusCode::try_from(status.code).unwrap()
+}
+
+#[test]
+fn handles_round_trip() {
+    let handle = <Arc<Counter> as Lower<UT>>::lower(Arc::new(Counter(7)));
+    let counter = <Arc<Counter> as Lift<UT>>::try_lift(handle).unwrap();
+    assert_eq!(counter.0, 7);
+
+    let mut status = FFIErrStatus::default();
+    free_counter(handle, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::Success);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn stale_handle_is_rejected() {
+    let handle = <Arc<Counter> as Lower<UT>>::lower(Arc::new(Counter(1)));
+    let mut status = FFIErrStatus::default();
+    free_counter(handle, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::Success);
+
+    // the freed slot is reused with a new generation
+    let other = <Arc<Counter> as Lower<UT>>::lower(Arc::new(Counter(2)));
+    assert_ne!(other, handle);
+    let err = <Arc<Counter> as Lift<UT>>::try_lift(handle).unwrap_err();
+    assert!(err.to_string().contains("stale or invalid handle"));
+    assert_eq!(<Arc<Counter> as Lift<UT>>::try_lift(other).unwrap().0, 2);
+    free_counter(other, &mut status);
+}
+
+#[test]
+fn double_free_is_reported() {
+    let handle = <Arc<Counter> as Lower<UT>>::lower(Arc::new(Counter(1)));
+    let mut status = FFIErrStatus::default();
+    free_counter(handle, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::Success);
+    free_counter(handle, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::UnexpectedError);
+}
+
+#[test]
+fn cloned_handles_are_freed_separately() {
+    let counter = Arc::new(Counter(3));
+    let handle = <Arc<Counter> as Lower<UT>>::lower(counter.clone());
+    let mut status = FFIErrStatus::default();
+    let cloned = clone_counter(handle, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::Success);
+    assert_eq!(Arc::strong_count(&counter), 3);
+
+    free_counter(handle, &mut status);
+    assert_eq!(<Arc<Counter> as Lift<UT>>::try_lift(cloned).unwrap().0, 3);
+    free_counter(cloned, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::Success);
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[test]
+fn handles_from_another_map_are_rejected() {
+    let other: HandleMap<u32> = HandleMap::for_type("handle_map::Other");
+    let handle = other.insert(1);
+    let err = <Arc<Counter> as Lift<UT>>::try_lift(handle).unwrap_err();
+    assert!(err.to_string().contains("belongs to a different type"));
+
+    let mut status = FFIErrStatus::default();
+    free_counter(handle, &mut status);
+    assert_eq!(status_code(&status), FFIStatusCode::UnexpectedError);
+    assert_eq!(other.remove(handle).unwrap(), 1);
+}
+
+#[test]
+fn null_handle_is_rejected() {
+    assert!(<Arc<Counter> as Lift<UT>>::try_lift(0).is_err());
+}
+
+#[test]
+fn handles_round_trip_in_buffers() {
+    let counters = vec![Arc::new(Counter(1)), Arc::new(Counter(2))];
+    let buf = write(counters.clone());
+    let lifted = read::<Vec<Arc<Counter>>>(&buf).unwrap();
+    assert!(Arc::ptr_eq(&lifted[0], &counters[0]));
+    assert!(Arc::ptr_eq(&lifted[1], &counters[1]));
+
+    let mut status = FFIErrStatus::default();
+    for handle in read::<Vec<u64>>(&buf).unwrap() {
+        free_counter(handle, &mut status);
+        assert_eq!(status_code(&status), FFIStatusCode::Success);
+    }
+    drop(lifted);
+    assert_eq!(Arc::strong_count(&counters[0]), 1);
+}
+
+#[test]
+fn mapped_object_metadata() {
+    let mut expected = vec![codes::TYPE_OBJECT];
+    expected.extend(meta_str("Counter"));
+    expected.push(codes::OBJECT_HANDLE_MAPPED);
+    assert_eq!(meta::<Counter>(), expected);
+    assert_eq!(meta::<Arc<Counter>>(), expected);
+}
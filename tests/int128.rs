@@ -0,0 +1,58 @@
+mod common;
+
+use common::{meta, round_trip, write, UT};
+use ffihelper::{
+    ffi::int128::{FFII128, FFIU128},
+    metadata::codes,
+    Lift, Lower,
+};
+
+#[test]
+fn u128_lowers_to_a_low_high_pair() {
+    let v = (7u128 << 64) | 9;
+    let pair = <u128 as Lower<UT>>::lower(v);
+    assert_eq!(pair, FFIU128 { low: 9, high: 7 });
+    assert_eq!(<u128 as Lift<UT>>::try_lift(pair).unwrap(), v);
+    assert_eq!(
+        <u128 as Lift<UT>>::try_lift(FFIU128 {
+            low: u64::MAX,
+            high: u64::MAX,
+        })
+        .unwrap(),
+        u128::MAX
+    );
+}
+
+#[test]
+fn i128_keeps_the_sign_in_the_high_half() {
+    let pair = <i128 as Lower<UT>>::lower(-2);
+    assert_eq!(
+        pair,
+        FFII128 {
+            low: u64::MAX - 1,
+            high: -1,
+        }
+    );
+    assert_eq!(<i128 as Lift<UT>>::try_lift(pair).unwrap(), -2);
+    for v in [i128::MIN, -1, 0, i128::MAX] {
+        assert_eq!(
+            <i128 as Lift<UT>>::try_lift(<i128 as Lower<UT>>::lower(v)).unwrap(),
+            v
+        );
+    }
+}
+
+#[test]
+fn wide_integers_are_written_big_endian() {
+    let v = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128;
+    assert_eq!(write(v), (1..=16).collect::<Vec<u8>>());
+    assert_eq!(round_trip(v), v);
+    assert_eq!(round_trip(i128::MIN), i128::MIN);
+    assert!(<u128 as Lift<UT>>::try_read(&mut &[0u8; 15][..]).is_err());
+}
+
+#[test]
+fn wide_integer_metadata() {
+    assert_eq!(meta::<u128>(), [codes::TYPE_U128]);
+    assert_eq!(meta::<i128>(), [codes::TYPE_I128]);
+}
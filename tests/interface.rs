@@ -0,0 +1,93 @@
+mod common;
+
+use std::{collections::HashMap, sync::Arc};
+
+use common::{meta, meta_str, read, write, UT};
+use ffihelper::{
+    ffi::call::{FFIErrStatus, FFIStatusCode},
+    ffi_trait,
+    metadata::codes,
+    Lift, Lower,
+};
+
+#[ffi_trait]
+pub trait Storage: Send + Sync {
+    fn get(&self, key: String) -> Option<String>;
+    fn clear(&self);
+}
+
+struct MemoryStorage(HashMap<String, String>);
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: String) -> Option<String> {
+        self.0.get(&key).cloned()
+    }
+
+    fn clear(&self) {}
+}
+
+fn storage() -> Arc<dyn Storage> {
+    Arc::new(MemoryStorage(HashMap::from([(
+        "a".to_owned(),
+        "1".to_owned(),
+    )])))
+}
+
+#[test]
+fn trait_objects_round_trip_through_handles() {
+    let storage = storage();
+    let handle = <Arc<dyn Storage> as Lower<UT>>::lower(storage.clone());
+    let lifted = <Arc<dyn Storage> as Lift<UT>>::try_lift(handle).unwrap();
+    assert!(Arc::ptr_eq(&lifted, &storage));
+    assert_eq!(lifted.get("a".to_owned()).as_deref(), Some("1"));
+    assert_eq!(lifted.get("b".to_owned()), None);
+
+    let mut status = FFIErrStatus::default();
+    let cloned = clone_storage(handle, &mut status);
+    free_storage(handle, &mut status);
+    assert_eq!(
+        FFIStatusCode::try_from(status.code),
+        Ok(FFIStatusCode::Success)
+    );
+    assert!(<Arc<dyn Storage> as Lift<UT>>::try_lift(handle).is_err());
+    assert!(<Arc<dyn Storage> as Lift<UT>>::try_lift(cloned).is_ok());
+    free_storage(cloned, &mut status);
+    drop(lifted);
+    assert_eq!(Arc::strong_count(&storage), 1);
+}
+
+#[test]
+fn trait_objects_round_trip_in_buffers() {
+    let storage = storage();
+    let buf = write(Some(storage.clone()));
+    let lifted = read::<Option<Arc<dyn Storage>>>(&buf).unwrap().unwrap();
+    assert!(Arc::ptr_eq(&lifted, &storage));
+
+    let mut status = FFIErrStatus::default();
+    free_storage(read::<Option<u64>>(&buf).unwrap().unwrap(), &mut status);
+    assert_eq!(
+        FFIStatusCode::try_from(status.code),
+        Ok(FFIStatusCode::Success)
+    );
+}
+
+#[test]
+fn interface_metadata_lists_methods() {
+    let mut expected = vec![codes::TYPE_INTERFACE];
+    expected.extend(meta_str("Storage"));
+    expected.extend([codes::OBJECT_HANDLE_MAPPED, 2]);
+    expected.extend(meta_str("get"));
+    expected.push(1);
+    expected.extend(meta_str("key"));
+    expected.extend([codes::TYPE_STRING, codes::TYPE_OPTION, codes::TYPE_STRING]);
+    expected.extend(meta_str("clear"));
+    expected.extend([0, codes::TYPE_UNIT]);
+    assert_eq!(meta::<dyn Storage>(), expected);
+    assert_eq!(meta::<Arc<dyn Storage>>(), expected);
+}
+
+#[test]
+fn invalid_traits_fail_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/interface_*.rs");
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn ip_addresses_round_trip() {
+    let v4 = Ipv4Addr::new(192, 168, 0, 1);
+    let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    assert_eq!(round_trip(v4), v4);
+    assert_eq!(round_trip(v6), v6);
+    assert_eq!(round_trip(IpAddr::V4(v4)), IpAddr::V4(v4));
+    assert_eq!(round_trip(IpAddr::V6(v6)), IpAddr::V6(v6));
+
+    assert_eq!(<Ipv4Addr as Lower<UT>>::lower(v4), 0xc0a8_0001);
+    assert_eq!(
+        <Ipv4Addr as Lift<UT>>::try_lift(0x7f00_0001).unwrap(),
+        Ipv4Addr::LOCALHOST
+    );
+}
+
+#[test]
+fn ip_addresses_are_tagged_by_version() {
+    assert_eq!(write(Ipv4Addr::new(10, 0, 0, 1)), [10, 0, 0, 1]);
+    assert_eq!(
+        write(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+        [4, 10, 0, 0, 1]
+    );
+    let v6 = write(IpAddr::V6(Ipv6Addr::LOCALHOST));
+    assert_eq!(v6.len(), 17);
+    assert_eq!((v6[0], v6[16]), (6, 1));
+
+    let err = read::<IpAddr>(&[5, 10, 0, 0, 1]).unwrap_err();
+    assert!(err.to_string().contains("unexpected tag byte for IpAddr"));
+    assert!(read::<IpAddr>(&[6, 0, 0]).is_err());
+}
+
+#[test]
+fn socket_addresses_round_trip() {
+    let v4 = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), 8080);
+    let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 7, 2);
+    assert_eq!(round_trip(v4), v4);
+    assert_eq!(round_trip(v6), v6);
+    assert_eq!(round_trip(SocketAddr::V4(v4)), SocketAddr::V4(v4));
+    assert_eq!(round_trip(SocketAddr::V6(v6)), SocketAddr::V6(v6));
+}
+
+#[test]
+fn socket_addresses_are_tagged_by_version() {
+    let v4 = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(1, 2, 3, 4), 0x1f90));
+    assert_eq!(write(v4), [4, 1, 2, 3, 4, 0x1f, 0x90]);
+
+    let v6 = write(SocketAddr::V6(SocketAddrV6::new(
+        Ipv6Addr::UNSPECIFIED,
+        1,
+        2,
+        3,
+    )));
+    assert_eq!(v6[0], 6);
+    assert_eq!(&v6[17..], [0, 1, 0, 0, 0, 2, 0, 0, 0, 3]);
+
+    assert!(read::<SocketAddr>(&[0, 1, 2, 3, 4, 0, 80]).is_err());
+    assert!(read::<SocketAddrV4>(&[1, 2, 3, 4, 0]).is_err());
+}
+
+#[test]
+fn address_metadata() {
+    assert_eq!(meta::<Ipv4Addr>(), [codes::TYPE_IPV4_ADDR]);
+    assert_eq!(meta::<Ipv6Addr>(), [codes::TYPE_IPV6_ADDR]);
+    assert_eq!(meta::<IpAddr>(), [codes::TYPE_IP_ADDR]);
+    assert_eq!(meta::<SocketAddrV4>(), [codes::TYPE_SOCKET_ADDR_V4]);
+    assert_eq!(meta::<SocketAddrV6>(), [codes::TYPE_SOCKET_ADDR_V6]);
+    assert_eq!(meta::<SocketAddr>(), [codes::TYPE_SOCKET_ADDR]);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use std::num::{NonZeroI64, NonZeroU128, NonZeroU32, NonZeroU8, NonZeroUsize, Wrapping};
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn non_zero_integers_round_trip() {
+    let id = NonZeroU32::new(42).unwrap();
+    assert_eq!(round_trip(id), id);
+    assert_eq!(<NonZeroU32 as Lower<UT>>::lower(id), 42);
+    assert_eq!(write(id), write(42u32));
+
+    let neg = NonZeroI64::new(-1).unwrap();
+    assert_eq!(round_trip(neg), neg);
+    let wide = NonZeroU128::new(u128::MAX).unwrap();
+    assert_eq!(round_trip(wide), wide);
+}
+
+#[test]
+fn zero_is_rejected() {
+    let err = <NonZeroU32 as Lift<UT>>::try_lift(0).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unexpected zero value for NonZeroU32"));
+    assert!(<NonZeroI64 as Lift<UT>>::try_lift(0).is_err());
+    assert!(<NonZeroU128 as Lift<UT>>::try_lift(0u128.into()).is_err());
+
+    let err = read::<NonZeroU8>(&[0]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unexpected zero value for NonZeroU8"));
+    assert!(read::<NonZeroUsize>(&[0; 8]).is_err());
+    assert!(read::<NonZeroI64>(&[0; 8]).is_err());
+}
+
+#[test]
+fn wrapping_uses_the_inner_encoding() {
+    assert_eq!(round_trip(Wrapping(u8::MAX)), Wrapping(u8::MAX));
+    assert_eq!(write(Wrapping(7u16)), write(7u16));
+}
+
+#[test]
+fn non_zero_metadata() {
+    assert_eq!(
+        meta::<NonZeroU32>(),
+        [codes::TYPE_NON_ZERO, codes::TYPE_U32]
+    );
+    assert_eq!(
+        meta::<NonZeroUsize>(),
+        [codes::TYPE_NON_ZERO, codes::TYPE_USIZE]
+    );
+    assert_eq!(
+        meta::<Wrapping<i8>>(),
+        [codes::TYPE_WRAPPING, codes::TYPE_I8]
+    );
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+use common::{meta, meta_str, read, UT};
+use ffihelper::{ffi_object, handle::RawObjectPtr, metadata::codes, Lift, Lower};
+
+#[derive(Debug, Default)]
+pub struct Counter {
+    value: AtomicU32,
+}
+
+ffi_object!(Counter);
+
+#[test]
+fn raw_handles_borrow_the_foreign_reference() {
+    let counter = Arc::new(Counter::default());
+    let ptr = <Arc<Counter> as Lower<UT>>::lower(counter.clone());
+    assert_eq!(Arc::strong_count(&counter), 2);
+
+    let lifted =
+        <Arc<Counter> as Lift<UT>>::try_lift(unsafe { RawObjectPtr::from_raw(ptr.as_ptr()) })
+            .unwrap();
+    assert!(Arc::ptr_eq(&lifted, &counter));
+    assert_eq!(Arc::strong_count(&counter), 3);
+    lifted.value.fetch_add(1, Ordering::Relaxed);
+    drop(lifted);
+
+    let cloned = unsafe { clone_counter(ptr.as_ptr()) };
+    assert_eq!(cloned, ptr.as_ptr());
+    assert_eq!(Arc::strong_count(&counter), 3);
+    unsafe {
+        free_counter(cloned);
+        free_counter(ptr.as_ptr());
+        free_counter(std::ptr::null());
+    }
+    assert_eq!(Arc::strong_count(&counter), 1);
+    assert_eq!(counter.value.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn null_raw_handles_are_rejected() {
+    let err =
+        <Arc<Counter> as Lift<UT>>::try_lift(unsafe { RawObjectPtr::from_raw(std::ptr::null()) })
+            .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("unexpected null handle for Counter"));
+}
+
+#[test]
+fn raw_handles_cannot_be_read_from_buffers() {
+    let err = read::<Arc<Counter>>(&[0; 8]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("only be passed as direct arguments"));
+}
+
+#[test]
+#[should_panic(expected = "can't be written to buffers")]
+fn raw_handles_cannot_be_written_to_buffers() {
+    <Arc<Counter> as Lower<UT>>::lower_into_buffer(Arc::new(Counter::default()));
+}
+
+#[test]
+fn object_metadata() {
+    let mut expected = vec![codes::TYPE_OBJECT];
+    expected.extend(meta_str("Counter"));
+    expected.push(codes::OBJECT_HANDLE_RAW);
+    assert_eq!(meta::<Counter>(), expected);
+    assert_eq!(meta::<Arc<Counter>>(), expected);
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use std::{ffi::OsString, path::PathBuf};
+
+use common::{meta, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn os_strings_round_trip() {
+    let s = OsString::from("héllo");
+    assert_eq!(round_trip(s.clone()), s);
+    let path = PathBuf::from("/tmp/some dir/file.txt");
+    assert_eq!(round_trip(path.clone()), path);
+
+    let buf = <PathBuf as Lower<UT>>::lower(path.clone());
+    assert_eq!(<PathBuf as Lift<UT>>::try_lift(buf).unwrap(), path);
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_os_strings_round_trip_on_unix() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let raw = vec![b'/', 0xff, 0xfe, b'x'];
+    let s = OsString::from_vec(raw.clone());
+    assert_eq!(write(s.clone()), [0, 0, 0, 4, b'/', 0xff, 0xfe, b'x']);
+    assert_eq!(round_trip(s.clone()), s);
+    let path = PathBuf::from(s);
+    assert_eq!(round_trip(path.clone()).into_os_string().into_vec(), raw);
+}
+
+#[cfg(windows)]
+#[test]
+fn unpaired_surrogates_round_trip_on_windows() {
+    use std::os::windows::ffi::OsStringExt;
+
+    let s = OsString::from_wide(&[0x61, 0xd800, 0x62]);
+    assert_eq!(write(s.clone()), [0, 0, 0, 6, 0, 0x61, 0xd8, 0, 0, 0x62]);
+    assert_eq!(round_trip(s.clone()), s);
+    assert!(common::read::<OsString>(&[0, 0, 0, 3, 0, 0x61, 0]).is_err());
+}
+
+#[test]
+fn os_string_metadata_records_the_encoding() {
+    let encoding = if cfg!(windows) {
+        codes::OS_STRING_UTF16
+    } else {
+        codes::OS_STRING_BYTES
+    };
+    assert_eq!(meta::<OsString>(), [codes::TYPE_OS_STRING, encoding]);
+    assert_eq!(meta::<PathBuf>(), [codes::TYPE_PATH_BUF, encoding]);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use std::ops::{Bound, Range, RangeInclusive};
+
+use common::{meta, read, round_trip, write};
+use ffihelper::{metadata::codes, types::BoundRange};
+
+fn bound_range<T>(start: Bound<T>, end: Bound<T>) -> BoundRange<T> {
+    BoundRange::from((start, end))
+}
+
+#[test]
+fn ranges_round_trip() {
+    assert_eq!(write(1u32..5), [0, 0, 0, 1, 0, 0, 0, 5]);
+    assert_eq!(round_trip(1u32..5), 1..5);
+    assert_eq!(round_trip(3u32..3), 3..3);
+    assert_eq!(round_trip(-1i8..=1), -1..=1);
+    assert_eq!(round_trip(2i8..=2), 2..=2);
+    assert_eq!(round_trip(0.5f64..1.5), 0.5..1.5);
+}
+
+#[test]
+fn bounds_are_tagged() {
+    assert_eq!(write(Bound::Included(1u8)), [0, 1]);
+    assert_eq!(write(Bound::Excluded(1u8)), [1, 1]);
+    assert_eq!(write(Bound::<u8>::Unbounded), [2]);
+    assert!(read::<Bound<u8>>(&[3]).is_err());
+
+    let range = bound_range(Bound::Excluded(1u8), Bound::Included(4));
+    assert_eq!(write(range.clone()), [1, 1, 0, 4]);
+    assert_eq!(round_trip(range.clone()), range);
+}
+
+#[test]
+fn start_after_end_is_rejected() {
+    let err = read::<Range<u32>>(&write((5u32, 1u32))).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("range start is greater than its end"));
+    assert!(read::<RangeInclusive<u32>>(&write((5u32, 1u32))).is_err());
+
+    let buf = write((Bound::Included(5u8), Bound::Excluded(1u8)));
+    assert!(read::<BoundRange<u8>>(&buf).is_err());
+}
+
+#[test]
+fn nan_bounds_are_rejected() {
+    for (start, end) in [(f64::NAN, 1.0), (0.0, f64::NAN), (f64::NAN, f64::NAN)] {
+        let err = read::<Range<f64>>(&write((start, end))).unwrap_err();
+        assert!(err.to_string().contains("range bounds are not comparable"));
+        assert!(read::<RangeInclusive<f64>>(&write((start, end))).is_err());
+        let buf = write((Bound::Included(start), Bound::Included(end)));
+        assert!(read::<BoundRange<f64>>(&buf).is_err());
+    }
+}
+
+#[test]
+fn unbounded_sides_are_not_compared() {
+    for range in [
+        bound_range(Bound::Unbounded, Bound::Unbounded),
+        bound_range(Bound::Included(5u8), Bound::Unbounded),
+        bound_range(Bound::Unbounded, Bound::Excluded(1)),
+    ] {
+        assert_eq!(round_trip(range.clone()), range);
+    }
+
+    // Order is only checked between two present bounds, so a NaN next to an unbounded side is kept.
+    let range = round_trip(bound_range(Bound::Included(f64::NAN), Bound::Unbounded));
+    assert!(matches!(range.start, Bound::Included(v) if v.is_nan()));
+}
+
+#[test]
+fn range_metadata() {
+    assert_eq!(meta::<Range<u32>>(), [codes::TYPE_RANGE, codes::TYPE_U32]);
+    assert_eq!(
+        meta::<RangeInclusive<i8>>(),
+        [codes::TYPE_RANGE_INCLUSIVE, codes::TYPE_I8]
+    );
+    assert_eq!(meta::<Bound<u8>>(), [codes::TYPE_BOUND, codes::TYPE_U8]);
+    assert_eq!(
+        meta::<BoundRange<f64>>(),
+        [codes::TYPE_BOUND_RANGE, codes::TYPE_F64]
+    );
+}
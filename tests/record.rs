@@ -0,0 +1,73 @@
+mod common;
+
+use common::{meta, meta_str, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, FFIRecord, Lift, Lower, TypeId};
+
+#[derive(Debug, Clone, PartialEq, FFIRecord)]
+struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, FFIRecord)]
+struct Pair(u8, String);
+
+#[derive(Debug, Clone, PartialEq, FFIRecord)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[test]
+fn records_round_trip() {
+    let point = Point {
+        x: -1,
+        y: 2,
+        label: Some("origin".to_owned()),
+    };
+    assert_eq!(round_trip(point.clone()), point);
+    let buf = <Point as Lower<UT>>::lower(point.clone());
+    assert_eq!(<Point as Lift<UT>>::try_lift(buf).unwrap(), point);
+
+    let pair = Pair(1, "a".to_owned());
+    assert_eq!(round_trip(pair.clone()), pair);
+    let wrapper = Wrapper { inner: vec![1u16] };
+    assert_eq!(round_trip(wrapper.clone()), wrapper);
+}
+
+#[test]
+fn fields_are_written_in_declaration_order() {
+    let point = Point {
+        x: 1,
+        y: 2,
+        label: None,
+    };
+    assert_eq!(write(point), [0, 0, 0, 1, 0, 0, 0, 2, 0]);
+    assert!(read::<Point>(&[0, 0, 0, 1, 0, 0, 0, 2]).is_err());
+}
+
+#[test]
+fn record_metadata() {
+    let mut expected = vec![codes::TYPE_RECORD];
+    expected.extend(meta_str("Point"));
+    // not extensible, three fields
+    expected.extend([0, 3]);
+    for (name, ty) in [
+        ("x", meta::<i32>()),
+        ("y", meta::<i32>()),
+        ("label", meta::<Option<String>>()),
+    ] {
+        expected.extend(meta_str(name));
+        // no default
+        expected.push(0);
+        expected.extend(ty);
+    }
+    assert_eq!(meta::<Point>(), expected);
+
+    let pair = meta::<Pair>();
+    assert!(pair.ends_with(&[meta_str("1"), vec![0, codes::TYPE_STRING]].concat()));
+    assert_eq!(
+        <Wrapper<u8> as TypeId<UT>>::TYPE_ID_META.as_ref().last(),
+        Some(&codes::TYPE_U8)
+    );
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn results_round_trip() {
+    assert_eq!(round_trip(Ok::<u32, String>(3)), Ok(3));
+    assert_eq!(
+        round_trip(Err::<u32, String>("bad".to_owned())),
+        Err("bad".to_owned())
+    );
+    let batch: Vec<Result<u8, bool>> = vec![Ok(1), Err(false), Ok(2)];
+    assert_eq!(round_trip(batch.clone()), batch);
+
+    let buf = <Result<u8, u8> as Lower<UT>>::lower(Err(4));
+    assert_eq!(<Result<u8, u8> as Lift<UT>>::try_lift(buf).unwrap(), Err(4));
+}
+
+#[test]
+fn results_are_tagged() {
+    assert_eq!(write(Ok::<u8, u16>(1)), [0, 1]);
+    assert_eq!(write(Err::<u8, u16>(1)), [1, 0, 1]);
+    let err = read::<Result<u8, u8>>(&[2, 1]).unwrap_err();
+    assert!(err.to_string().contains("unexpected tag byte for Result"));
+    assert!(read::<Result<u8, u8>>(&[]).is_err());
+}
+
+#[test]
+fn result_metadata_combines_both_types() {
+    assert_eq!(
+        meta::<Result<u32, String>>(),
+        [codes::TYPE_RESULT, codes::TYPE_U32, codes::TYPE_STRING]
+    );
+    assert_eq!(
+        meta::<Vec<Result<Option<u8>, bool>>>(),
+        [
+            codes::TYPE_VEC,
+            codes::TYPE_RESULT,
+            codes::TYPE_OPTION,
+            codes::TYPE_U8,
+            codes::TYPE_BOOL
+        ]
+    );
+}
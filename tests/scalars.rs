@@ -0,0 +1,59 @@
+mod common;
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn char_round_trip() {
+    for c in ['a', 'é', '\u{10ffff}', '😀'] {
+        assert_eq!(round_trip(c), c);
+    }
+    assert_eq!(<char as Lower<UT>>::lower('😀'), 0x1f600);
+    assert_eq!(write('a'), [0, 0, 0, 0x61]);
+}
+
+#[test]
+fn invalid_scalar_values_are_rejected() {
+    for v in [0xd800, 0xdfff, 0x11_0000, u32::MAX] {
+        let err = <char as Lift<UT>>::try_lift(v).unwrap_err();
+        assert!(err.to_string().contains("invalid unicode scalar value"));
+        assert!(read::<char>(&v.to_be_bytes()).is_err());
+    }
+}
+
+#[test]
+fn pointer_sized_integers_use_64_bits() {
+    assert_eq!(write(1usize), [0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(write(-1isize), [0xff; 8]);
+    for v in [0, 1, usize::MAX] {
+        assert_eq!(round_trip(v), v);
+    }
+    for v in [isize::MIN, -1, 0, isize::MAX] {
+        assert_eq!(round_trip(v), v);
+    }
+    assert!(<usize as Lift<UT>>::try_read(&mut &[0u8; 4][..]).is_err());
+}
+
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn out_of_range_pointer_sized_integers_are_rejected() {
+    let err = <usize as Lift<UT>>::try_lift(1 << 32).unwrap_err();
+    assert!(err.to_string().contains("out of range for usize"));
+    assert!(read::<usize>(&(1u64 << 32).to_be_bytes()).is_err());
+    assert!(<isize as Lift<UT>>::try_lift(i64::MIN).is_err());
+    assert!(read::<isize>(&i64::MAX.to_be_bytes()).is_err());
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn full_range_pointer_sized_integers_are_accepted() {
+    assert_eq!(<usize as Lift<UT>>::try_lift(u64::MAX).unwrap(), usize::MAX);
+    assert_eq!(<isize as Lift<UT>>::try_lift(i64::MIN).unwrap(), isize::MIN);
+}
+
+#[test]
+fn scalar_metadata() {
+    assert_eq!(meta::<char>(), [codes::TYPE_CHAR]);
+    assert_eq!(meta::<usize>(), [codes::TYPE_USIZE]);
+    assert_eq!(meta::<isize>(), [codes::TYPE_ISIZE]);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use common::{meta, read, round_trip, write};
+use ffihelper::metadata::codes;
+
+/// The `(i64 seconds, u32 nanoseconds)` wire layout of a `SystemTime`.
+fn time_bytes(secs: i64, nanos: u32) -> Vec<u8> {
+    let mut buf = secs.to_be_bytes().to_vec();
+    buf.extend(nanos.to_be_bytes());
+    buf
+}
+
+#[test]
+fn durations_round_trip() {
+    for d in [
+        Duration::ZERO,
+        Duration::new(5, 250),
+        Duration::new(u64::MAX, 999_999_999),
+    ] {
+        assert_eq!(round_trip(d), d);
+    }
+    assert_eq!(
+        write(Duration::new(1, 2)),
+        [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2]
+    );
+}
+
+#[test]
+fn durations_with_too_many_nanos_are_rejected() {
+    let mut buf = 1u64.to_be_bytes().to_vec();
+    buf.extend(1_000_000_000u32.to_be_bytes());
+    assert!(read::<Duration>(&buf).is_err());
+    assert!(read::<Duration>(&buf[..11]).is_err());
+}
+
+#[test]
+fn times_round_trip() {
+    let now = SystemTime::now();
+    assert_eq!(round_trip(now), now);
+    let later = UNIX_EPOCH + Duration::new(1_700_000_000, 123);
+    assert_eq!(write(later), time_bytes(1_700_000_000, 123));
+    assert_eq!(round_trip(later), later);
+}
+
+#[test]
+fn pre_epoch_times_round_trip() {
+    // nanoseconds count forward from the (negative) second
+    let before = UNIX_EPOCH - Duration::new(1, 500_000_000);
+    assert_eq!(write(before), time_bytes(-2, 500_000_000));
+    assert_eq!(round_trip(before), before);
+
+    let whole = UNIX_EPOCH - Duration::from_secs(86_400);
+    assert_eq!(write(whole), time_bytes(-86_400, 0));
+    assert_eq!(round_trip(whole), whole);
+}
+
+// Only unix timestamps reach all of the i64 seconds range.
+#[cfg(unix)]
+#[test]
+fn extreme_times_round_trip() {
+    for (secs, nanos) in [(i64::MIN, 0), (i64::MIN, 1), (i64::MAX, 999_999_999)] {
+        let time = read::<SystemTime>(&time_bytes(secs, nanos)).unwrap();
+        assert_eq!(write(time), time_bytes(secs, nanos));
+    }
+}
+
+#[test]
+fn out_of_range_times_are_rejected() {
+    let err = read::<SystemTime>(&time_bytes(0, 1_000_000_000)).unwrap_err();
+    assert!(err.to_string().contains("nanoseconds out of range"));
+    assert!(read::<SystemTime>(&time_bytes(-1, u32::MAX)).is_err());
+    assert!(read::<SystemTime>(&time_bytes(0, 0)[..8]).is_err());
+}
+
+#[test]
+fn time_metadata() {
+    assert_eq!(meta::<Duration>(), [codes::TYPE_DURATION]);
+    assert_eq!(meta::<SystemTime>(), [codes::TYPE_SYSTEM_TIME]);
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use common::{meta, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift};
+
+#[test]
+fn tuples_round_trip() {
+    assert_eq!(round_trip((7u8,)), (7,));
+    assert_eq!(round_trip(("a".to_owned(), 3u32)), ("a".to_owned(), 3));
+    let twelve = (
+        1u8, 2i8, 3u16, 4i16, 5u32, 6i32, 7u64, 8i64, 9.0f32, 10.0f64, true, 'c',
+    );
+    assert_eq!(round_trip(twelve), twelve);
+}
+
+#[test]
+fn tuple_elements_are_written_in_order() {
+    assert_eq!(write((1u8, 2u16)), [1, 0, 2]);
+    assert!(<(u8, u16) as Lift<UT>>::try_read(&mut &[1u8, 0][..]).is_err());
+}
+
+#[test]
+fn tuple_metadata_records_the_arity() {
+    assert_eq!(meta::<(u8,)>(), [codes::TYPE_TUPLE, 1, codes::TYPE_U8]);
+    assert_eq!(
+        meta::<(String, u32)>(),
+        [codes::TYPE_TUPLE, 2, codes::TYPE_STRING, codes::TYPE_U32]
+    );
+    let meta = meta::<(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, (u8, bool))>();
+    assert_eq!(&meta[..2], [codes::TYPE_TUPLE, 12]);
+    assert_eq!(
+        &meta[13..],
+        [codes::TYPE_TUPLE, 2, codes::TYPE_U8, codes::TYPE_BOOL]
+    );
+}
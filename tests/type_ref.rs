@@ -0,0 +1,118 @@
+mod common;
+
+use common::{meta, meta_str, round_trip};
+use ffihelper::{metadata::codes, FFIEnum, FFIRecord};
+
+#[derive(Debug, Clone, PartialEq, FFIRecord)]
+struct Tree {
+    value: u32,
+    children: Vec<Tree>,
+    #[ffi(type_ref = Forest)]
+    forest: Option<Box<Forest>>,
+}
+
+#[derive(Debug, Clone, PartialEq, FFIRecord)]
+struct Forest {
+    trees: Vec<Tree>,
+}
+
+#[derive(Debug, Clone, PartialEq, FFIEnum)]
+enum Expr {
+    Num(i64),
+    Neg(Box<Self>),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+fn type_ref(name: &str) -> Vec<u8> {
+    let mut res = vec![codes::TYPE_REF];
+    res.extend(meta_str(name));
+    res
+}
+
+#[test]
+fn recursive_records_round_trip() {
+    let tree = Tree {
+        value: 1,
+        children: vec![Tree {
+            value: 2,
+            children: Vec::new(),
+            forest: None,
+        }],
+        forest: Some(Box::new(Forest {
+            trees: vec![Tree {
+                value: 3,
+                children: Vec::new(),
+                forest: None,
+            }],
+        })),
+    };
+    assert_eq!(round_trip(tree.clone()), tree);
+}
+
+#[test]
+fn recursive_records_are_referenced_by_name() {
+    let mut expected = vec![codes::TYPE_RECORD];
+    expected.extend(meta_str("Tree"));
+    expected.extend([0, 3]);
+    expected.extend(meta_str("value"));
+    expected.extend([0, codes::TYPE_U32]);
+    expected.extend(meta_str("children"));
+    expected.extend([0, codes::TYPE_VEC]);
+    expected.extend(type_ref("Tree"));
+    expected.extend(meta_str("forest"));
+    expected.extend([0, codes::TYPE_OPTION]);
+    expected.extend(type_ref("Forest"));
+    assert_eq!(meta::<Tree>(), expected);
+
+    // `Forest` inlines `Tree`, whose own metadata refers back to `Forest`.
+    let mut expected = vec![codes::TYPE_RECORD];
+    expected.extend(meta_str("Forest"));
+    expected.extend([0, 1]);
+    expected.extend(meta_str("trees"));
+    expected.extend([0, codes::TYPE_VEC]);
+    expected.extend(meta::<Tree>());
+    assert_eq!(meta::<Forest>(), expected);
+}
+
+#[test]
+fn recursive_enums_are_referenced_by_name() {
+    let expr = Expr::Add(
+        Box::new(Expr::Num(1)),
+        Box::new(Expr::Neg(Box::new(Expr::Num(2)))),
+    );
+    assert_eq!(round_trip(expr.clone()), expr);
+
+    let mut expected = vec![codes::TYPE_ENUM];
+    expected.extend(meta_str("Expr"));
+    expected.extend([0, 3]);
+    expected.extend(meta_str("Num"));
+    expected.push(1);
+    expected.extend(meta_str("0"));
+    expected.push(codes::TYPE_I64);
+    expected.extend(meta_str("Neg"));
+    expected.push(1);
+    expected.extend(meta_str("0"));
+    expected.extend(type_ref("Expr"));
+    expected.extend(meta_str("Add"));
+    expected.push(2);
+    for field in ["0", "1"] {
+        expected.extend(meta_str(field));
+        expected.extend(type_ref("Expr"));
+    }
+    assert_eq!(meta::<Expr>(), expected);
+}
+
+#[test]
+fn qualified_paths_are_not_type_refs() {
+    #[derive(FFIRecord)]
+    struct Duration {
+        inner: std::time::Duration,
+    }
+
+    let mut expected = vec![codes::TYPE_RECORD];
+    expected.extend(meta_str("Duration"));
+    expected.extend([0, 1]);
+    expected.extend(meta_str("inner"));
+    expected.extend([0, codes::TYPE_DURATION]);
+    assert_eq!(meta::<Duration>(), expected);
+}
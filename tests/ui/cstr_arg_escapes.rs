@@ -0,0 +1,15 @@
+use std::ffi::CStr;
+
+use ffihelper::{ffi::cstring::FFICStr, types::CStrArg, Lift};
+
+struct UT;
+
+fn escape_arg(arg: CStrArg<'_>) -> &'static CStr {
+    arg.as_c_str()
+}
+
+fn escape_ptr(ptr: FFICStr<'_>) -> CStrArg<'static> {
+    <CStrArg<'_> as Lift<UT>>::try_lift(ptr).unwrap()
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ffihelper::FFIEnum;
+
+#[derive(FFIEnum)]
+#[repr(u128)]
+enum Huge {
+    A = 0,
+    B = 1,
+}
+
+fn main() {}
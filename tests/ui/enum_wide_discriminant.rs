@@ -0,0 +1,10 @@
+use ffihelper::FFIEnum;
+
+#[derive(FFIEnum)]
+#[repr(i64)]
+enum Big {
+    A = 0,
+    B = 1 << 32,
+}
+
+fn main() {}
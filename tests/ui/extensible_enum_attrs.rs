@@ -0,0 +1,17 @@
+use ffihelper::FFIEnum;
+
+#[derive(FFIEnum)]
+#[ffi(extensible)]
+enum Extensible {
+    A,
+}
+
+#[derive(FFIEnum)]
+enum Defaulted {
+    A {
+        #[ffi(default)]
+        value: u32,
+    },
+}
+
+fn main() {}
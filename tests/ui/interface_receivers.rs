@@ -0,0 +1,18 @@
+use ffihelper::ffi_trait;
+
+#[ffi_trait]
+pub trait MutSelf: Send + Sync {
+    fn set(&mut self, value: u32);
+}
+
+#[ffi_trait]
+pub trait ValueSelf: Send + Sync {
+    fn into_value(self) -> u32;
+}
+
+#[ffi_trait]
+pub trait NoSelf: Send + Sync {
+    fn create() -> u32;
+}
+
+fn main() {}
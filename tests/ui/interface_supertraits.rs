@@ -0,0 +1,13 @@
+use ffihelper::ffi_trait;
+
+#[ffi_trait]
+pub trait NotSync: Send {
+    fn get(&self) -> u32;
+}
+
+#[ffi_trait]
+pub trait NotSend: Sync {
+    fn get(&self) -> u32;
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+mod common;
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{metadata::codes, Lift, Lower};
+
+#[test]
+fn unit_is_zero_bytes() {
+    assert!(write(()).is_empty());
+    round_trip(());
+    <() as Lower<UT>>::lower(());
+    <() as Lift<UT>>::try_lift(()).unwrap();
+}
+
+#[test]
+fn unit_inside_containers() {
+    assert_eq!(write(Some(())), [1]);
+    assert_eq!(round_trip(Some(())), Some(()));
+    assert_eq!(round_trip(None::<()>), None);
+
+    assert_eq!(write(vec![(), (), ()]), [0, 0, 0, 3]);
+    assert_eq!(round_trip(vec![(), ()]), vec![(), ()]);
+
+    assert_eq!(write(Ok::<(), u8>(())), [0]);
+    assert_eq!(round_trip(Ok::<(), u8>(())), Ok(()));
+    assert_eq!(round_trip(Err::<(), u8>(3)), Err(3));
+    assert_eq!(read::<Result<(), u8>>(&[1, 9]).unwrap(), Err(9));
+}
+
+#[test]
+fn unit_metadata() {
+    assert_eq!(meta::<()>(), [codes::TYPE_UNIT]);
+    assert_eq!(meta::<Option<()>>(), [codes::TYPE_OPTION, codes::TYPE_UNIT]);
+    assert_eq!(meta::<Vec<()>>(), [codes::TYPE_VEC, codes::TYPE_UNIT]);
+    assert_eq!(
+        meta::<Result<(), String>>(),
+        [codes::TYPE_RESULT, codes::TYPE_UNIT, codes::TYPE_STRING]
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::{meta, read, round_trip, write, UT};
+use ffihelper::{ffi::buffer::FFIBuffer, metadata::codes, types::Utf16String, Lift, Lower};
+
+fn utf16(s: &str) -> Utf16String {
+    Utf16String(s.to_owned())
+}
+
+fn lift(bytes: Vec<u8>) -> ffihelper::FFIResult<Utf16String> {
+    <Utf16String as Lift<UT>>::try_lift(FFIBuffer::from_vec(bytes))
+}
+
+#[test]
+fn strings_are_big_endian_code_units() {
+    let buf = <Utf16String as Lower<UT>>::lower(utf16("hé"));
+    assert_eq!(buf.destroy_into_vec(), [0, b'h', 0, 0xe9]);
+    assert_eq!(write(utf16("hé")), [0, 0, 0, 2, 0, b'h', 0, 0xe9]);
+    assert_eq!(round_trip(utf16("")).0, "");
+}
+
+#[test]
+fn non_bmp_characters_use_surrogate_pairs() {
+    assert_eq!(write(utf16("😀")), [0, 0, 0, 2, 0xd8, 0x3d, 0xde, 0x00]);
+    assert_eq!(round_trip(utf16("a😀b")).0, "a😀b");
+    assert_eq!(lift(vec![0xd8, 0x3d, 0xde, 0x00]).unwrap().0, "😀");
+}
+
+#[test]
+fn lone_surrogates_are_rejected() {
+    // High surrogate without its low half, at the end and followed by a regular unit.
+    assert!(lift(vec![0xd8, 0x3d]).is_err());
+    assert!(lift(vec![0xd8, 0x3d, 0, b'a']).is_err());
+    // Low surrogate without a preceding high one.
+    assert!(lift(vec![0xde, 0x00]).is_err());
+    assert!(read::<Utf16String>(&[0, 0, 0, 1, 0xdc, 0x00]).is_err());
+}
+
+#[test]
+fn odd_byte_counts_are_rejected() {
+    let err = lift(vec![0, b'a', 0]).unwrap_err();
+    assert!(err.to_string().contains("odd number of bytes"));
+    // Inside buffers the length counts units, so a short buffer runs out instead.
+    assert!(read::<Utf16String>(&[0, 0, 0, 2, 0, b'a', 0]).is_err());
+}
+
+#[test]
+fn utf16_string_metadata() {
+    assert_eq!(meta::<Utf16String>(), [codes::TYPE_UTF16_STRING]);
+}